@@ -0,0 +1,123 @@
+//! Multi-approver invoice approval policies
+
+use soroban_sdk::{contractimpl, Address, Env, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    ApprovalPolicy, Invoice, InvoiceApproval,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Set the approval policy applied to invoices addressed to `client`
+    ///
+    /// Invoices of at least `min_amount` need `threshold` distinct approvers
+    /// from the list, in list order when `ordered` is set. Smaller invoices
+    /// need a single approval from any listed approver.
+    pub fn set_approval_policy(
+        env: Env,
+        client: Address,
+        policy: ApprovalPolicy,
+    ) -> Result<(), ContractError> {
+        // Verify client authorization
+        validation::validate_signature(&env, &client, &[], &[])?;
+
+        let approver_count = policy.approvers.len();
+        if approver_count == 0
+            || policy.threshold == 0
+            || policy.threshold > approver_count
+            || policy.min_amount < 0
+        {
+            return Err(ContractError::InvalidApprovalPolicy);
+        }
+
+        // Each approver may only be listed once
+        for (i, approver) in policy.approvers.iter().enumerate() {
+            if policy.approvers.first_index_of(&approver) != Some(i as u32) {
+                return Err(ContractError::InvalidApprovalPolicy);
+            }
+            validation::validate_address(&env, &approver)?;
+        }
+
+        storage::set_approval_policy(&env, &client, &policy);
+        Ok(())
+    }
+
+    /// Remove the client's approval policy, restoring single-signer approval
+    pub fn remove_approval_policy(env: Env, client: Address) -> Result<(), ContractError> {
+        // Verify client authorization
+        validation::validate_signature(&env, &client, &[], &[])?;
+
+        storage::remove_approval_policy(&env, &client);
+        Ok(())
+    }
+
+    /// Get the client's approval policy, if any
+    pub fn get_approval_policy(env: Env, client: Address) -> Option<ApprovalPolicy> {
+        storage::get_approval_policy(&env, &client)
+    }
+
+    /// Get every individual approval recorded for an invoice
+    pub fn get_invoice_approvals(env: Env, invoice_id: u64) -> Result<Vec<InvoiceApproval>, ContractError> {
+        if storage::get_invoice(&env, invoice_id).is_none() {
+            return Err(ContractError::InvoiceNotFound);
+        }
+        Ok(storage::get_invoice_approvals(&env, invoice_id))
+    }
+
+    /// Whether `signer` may approve or reject the invoice for its client
+    pub(crate) fn is_invoice_approver(env: &Env, invoice: &Invoice, signer: &Address) -> bool {
+        let Some(client) = &invoice.client else {
+            return false;
+        };
+        match storage::get_approval_policy(env, client) {
+            Some(policy) => policy.approvers.contains(signer),
+            None => client == signer,
+        }
+    }
+
+    /// Record an approval of the invoice's current version and return
+    /// whether the client's approval policy is now satisfied
+    pub(crate) fn record_invoice_approval(
+        env: &Env,
+        invoice: &Invoice,
+        approver: &Address,
+    ) -> Result<bool, ContractError> {
+        let policy = invoice
+            .client
+            .as_ref()
+            .and_then(|client| storage::get_approval_policy(env, client));
+        let mut approvals = storage::get_invoice_approvals(env, invoice.id);
+
+        // Only approvals of the current version count
+        let mut current: u32 = 0;
+        for approval in approvals.iter() {
+            if approval.version == invoice.version {
+                if approval.approver == *approver {
+                    return Err(ContractError::DuplicateApproval);
+                }
+                current += 1;
+            }
+        }
+
+        let required = match &policy {
+            Some(policy) if invoice.amount >= policy.min_amount => {
+                if policy.ordered && policy.approvers.get(current) != Some(approver.clone()) {
+                    return Err(ContractError::ApprovalOutOfOrder);
+                }
+                policy.threshold
+            }
+            _ => 1,
+        };
+
+        approvals.push_back(InvoiceApproval {
+            approver: approver.clone(),
+            version: invoice.version,
+            timestamp: env.ledger().timestamp(),
+        });
+        storage::set_invoice_approvals(env, invoice.id, &approvals);
+
+        Ok(current + 1 >= required)
+    }
+}
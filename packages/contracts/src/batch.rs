@@ -0,0 +1,100 @@
+//! Bulk invoice creation and expiration sweeps
+
+use soroban_sdk::{contractimpl, Address, Env, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    InvoiceSpec, SweepResult,
+};
+use crate::validation;
+use crate::storage;
+
+/// Maximum number of invoices created by one batch call
+///
+/// Each invoice writes up to four ledger entries (record, history, creator
+/// number index and external reference), and the call also writes the
+/// creator's number sequence, instance storage and the reentrancy guard.
+/// A full batch therefore writes 43 of the 50 entries allowed per
+/// transaction; eleven would leave a single entry of headroom.
+pub const MAX_INVOICE_BATCH: u32 = 10;
+
+/// Maximum number of invoice IDs scanned by one sweep call
+///
+/// Each expired invoice writes its record and history entry.
+pub const MAX_SWEEP_ITEMS: u32 = 20;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Create several draft invoices from one creator in a single call
+    ///
+    /// Every spec is validated as in `create_invoice`; if any is invalid,
+    /// none are created. Returns the new invoice IDs in spec order.
+    pub fn create_invoices_batch(
+        env: Env,
+        creator: Address,
+        specs: Vec<InvoiceSpec>,
+    ) -> Result<Vec<u64>, ContractError> {
+        // Verify creator authorization before allocating numbers or references
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        if specs.is_empty() || specs.len() > MAX_INVOICE_BATCH {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut invoice_ids = Vec::new(&env);
+        for spec in specs.iter() {
+            match Self::create_draft_invoice(
+                &env,
+                creator.clone(),
+                spec.client,
+                spec.amount,
+                spec.description,
+                spec.due_date,
+                spec.external_ref,
+            ) {
+                Ok(invoice_id) => invoice_ids.push_back(invoice_id),
+                Err(err) => {
+                    validation::clear_reentrancy(&env);
+                    return Err(err);
+                }
+            }
+        }
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(invoice_ids)
+    }
+
+    /// Expire every overdue invoice among up to `max_items` IDs starting at
+    /// `start_id` (callable by anyone)
+    ///
+    /// `max_items` is clamped to 1..=20 to keep the sweep within the resource budget.
+    /// Call again with the returned `next_id` until it is `None`.
+    pub fn sweep_expired_invoices(env: Env, start_id: u64, max_items: u32) -> SweepResult {
+        let last_id = storage::get_invoice_count(&env);
+        let start_id = start_id.max(1);
+        let end_id = start_id.saturating_add(max_items.clamp(1, MAX_SWEEP_ITEMS) as u64);
+        let now = env.ledger().timestamp();
+
+        let mut expired = Vec::new(&env);
+        for invoice_id in start_id..end_id.min(last_id.saturating_add(1)) {
+            let Some(mut invoice) = storage::get_invoice(&env, invoice_id) else {
+                continue;
+            };
+            if Self::is_expirable(&invoice) && now > invoice.due_date {
+                invoice.status = Self::lapsed_status(&invoice);
+                Self::save_invoice_transition(&env, &invoice, None, None);
+                expired.push_back(invoice_id);
+            }
+        }
+
+        SweepResult {
+            expired,
+            next_id: if end_id <= last_id { Some(end_id) } else { None },
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Invoices addressed to an approval key instead of a client address
+
+use soroban_sdk::{contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    InvoiceResult, InvoiceStatus,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Create an invoice for a client who is not yet onboarded
+    ///
+    /// `claim_key` is the ed25519 public key of a one-off approval keypair
+    /// whose secret key is sent to the client out of band (e.g. by email).
+    /// The first wallet to claim it through `claim_invoice` with a signature
+    /// of its own address becomes the invoice client.
+    pub fn create_claimable_invoice(
+        env: Env,
+        creator: Address,
+        claim_key: BytesN<32>,
+        amount: i128,
+        description: String,
+        due_date: u64,
+    ) -> Result<InvoiceResult, ContractError> {
+        // Verify creator authorization before allocating a number
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        // Validate inputs
+        validation::validate_address(&env, &creator)?;
+        validation::validate_amount(amount)?;
+
+        // Validate due date is in the future
+        if due_date <= env.ledger().timestamp() {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Create and store invoice record without a client
+        let mut invoice = Self::build_invoice(
+            &env,
+            creator.clone(),
+            None,
+            amount,
+            description,
+            due_date,
+            InvoiceStatus::Draft,
+        );
+        invoice.claim_key = Some(claim_key);
+        let invoice_id = invoice.id;
+        Self::save_invoice_transition(&env, &invoice, Some(creator), None);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(InvoiceResult {
+            invoice_id,
+            status: InvoiceStatus::Draft,
+            tx_hash: None,
+        })
+    }
+
+    /// Bind the caller as client of a claimable invoice with a signature by
+    /// its approval key
+    ///
+    /// `signature` signs the `(contract, invoice_id, claimant)` tuple
+    /// serialized as XDR, so a claim seen in the mempool cannot be replayed
+    /// by another wallet.
+    pub fn claim_invoice(
+        env: Env,
+        invoice_id: u64,
+        claimant: Address,
+        signature: BytesN<64>,
+    ) -> Result<InvoiceResult, ContractError> {
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify claimant authorization
+        validation::validate_signature(&env, &claimant, &[], &[])?;
+
+        if invoice.client.is_some() {
+            return Err(ContractError::InvoiceAlreadyClaimed);
+        }
+
+        if invoice.status != InvoiceStatus::Draft && invoice.status != InvoiceStatus::Sent {
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        // The holdback arbiter may not become a party to the dispute
+        if invoice.holdback_arbiter.as_ref() == Some(&claimant) {
+            return Err(ContractError::InvalidArbiter);
+        }
+
+        // Verify the approval key signed this invoice and claimant
+        let claim_key = invoice.claim_key.clone().ok_or(ContractError::Unauthorized)?;
+        let message: Bytes = (env.current_contract_address(), invoice_id, claimant.clone()).to_xdr(&env);
+        validation::validate_claim_signature(&env, &claim_key, &message, &signature);
+
+        invoice.client = Some(claimant.clone());
+        let note = String::from_str(&env, "claimed");
+        Self::save_invoice_transition(&env, &invoice, Some(claimant), Some(note));
+
+        Ok(InvoiceResult {
+            invoice_id,
+            status: invoice.status,
+            tx_hash: None,
+        })
+    }
+}
@@ -0,0 +1,225 @@
+//! Credit note functions for executed invoices
+
+use soroban_sdk::{contractimpl, Address, Env, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    CreditDisposition, CreditNote, CreditNoteResult, CreditNoteStatus, HoldbackStatus, Invoice,
+    InvoiceStatus, TransactionLink, TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Issue a credit note against an executed invoice (called by creator)
+    ///
+    /// A `Refund` credit is paid back to the client immediately. An
+    /// `ApplyToNextInvoice` credit is deducted from the next invoice the
+    /// creator executes for the same client in the same denomination asset.
+    /// Credits issued against an invoice can never exceed the amount the
+    /// client paid for it. Split invoices have no single client to credit.
+    pub fn issue_credit_note(
+        env: Env,
+        creator: Address,
+        invoice_id: u64,
+        amount: i128,
+        reason: String,
+        disposition: CreditDisposition,
+    ) -> Result<CreditNoteResult, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify creator authorization
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        if invoice.creator != creator {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Held funds are settled by the dispute process, not by credit notes
+        if invoice.status != InvoiceStatus::Executed
+            || matches!(invoice.holdback_status, HoldbackStatus::Holding | HoldbackStatus::Disputed)
+        {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        if !invoice.shares.is_empty() {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::SplitInvoiceNotCreditable);
+        }
+
+        // Validate amount against what was actually paid
+        let client = Self::invoice_client(&invoice)?;
+        validation::validate_amount(amount)?;
+        if invoice.credited_amount + amount > invoice.paid_amount {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::CreditExceedsPayment);
+        }
+
+        let credit_note_id = storage::get_next_credit_note_id(&env);
+        let mut credit_note = CreditNote {
+            id: credit_note_id,
+            invoice_id,
+            creator: creator.clone(),
+            client: client.clone(),
+            asset: invoice.denomination_asset.clone(),
+            amount,
+            remaining: amount,
+            reason: reason.clone(),
+            disposition: disposition.clone(),
+            status: CreditNoteStatus::Pending,
+            created_at: env.ledger().timestamp(),
+            refund_tx_id: None,
+        };
+
+        match disposition {
+            CreditDisposition::Refund => {
+                // Refund the client (in real implementation, transfer from creator to client)
+                let refund = Self::record_transaction(
+                    &env,
+                    TransactionType::Invoice,
+                    creator.clone(),
+                    client,
+                    amount,
+                    reason,
+                    TransactionLink::None,
+                );
+                credit_note.remaining = 0;
+                credit_note.status = CreditNoteStatus::Refunded;
+                credit_note.refund_tx_id = Some(refund.id);
+            }
+            CreditDisposition::ApplyToNextInvoice => {
+                let asset = &invoice.denomination_asset;
+                let mut pending = storage::get_pending_credits(&env, &creator, &client, asset);
+                pending.push_back(credit_note_id);
+                storage::set_pending_credits(&env, &creator, &client, asset, &pending);
+            }
+        }
+        storage::set_credit_note(&env, credit_note_id, &credit_note);
+
+        let mut issued = storage::get_invoice_credit_notes(&env, invoice_id);
+        issued.push_back(credit_note_id);
+        storage::set_invoice_credit_notes(&env, invoice_id, &issued);
+
+        invoice.credited_amount += amount;
+        storage::set_invoice(&env, invoice_id, &invoice);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(CreditNoteResult {
+            credit_note_id,
+            status: credit_note.status,
+        })
+    }
+
+    /// Get credit note details
+    pub fn get_credit_note(env: Env, credit_note_id: u64) -> Result<CreditNote, ContractError> {
+        storage::get_credit_note(&env, credit_note_id)
+            .ok_or(ContractError::CreditNoteNotFound)
+    }
+
+    /// Get all credit notes issued against an invoice
+    pub fn get_invoice_credit_notes(env: Env, invoice_id: u64) -> Result<Vec<CreditNote>, ContractError> {
+        if storage::get_invoice(&env, invoice_id).is_none() {
+            return Err(ContractError::InvoiceNotFound);
+        }
+
+        let mut credit_notes = Vec::new(&env);
+        for id in storage::get_invoice_credit_notes(&env, invoice_id).iter() {
+            if let Some(credit_note) = storage::get_credit_note(&env, id) {
+                credit_notes.push_back(credit_note);
+            }
+        }
+        Ok(credit_notes)
+    }
+
+    /// Get the net amount settled on an invoice (paid minus credited)
+    pub fn get_invoice_net_settled(env: Env, invoice_id: u64) -> Result<i128, ContractError> {
+        let invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+        Ok(invoice.paid_amount - invoice.credited_amount)
+    }
+
+    /// Return credit consumed by an invoice that was never paid to the
+    /// client's pending credits, as a new credit note against that invoice
+    pub(crate) fn restore_pending_credit(env: &Env, invoice: &Invoice, amount: i128) {
+        let Some(client) = invoice.client.clone() else {
+            return;
+        };
+        if amount <= 0 {
+            return;
+        }
+
+        let credit_note = CreditNote {
+            id: storage::get_next_credit_note_id(env),
+            invoice_id: invoice.id,
+            creator: invoice.creator.clone(),
+            client: client.clone(),
+            asset: invoice.denomination_asset.clone(),
+            amount,
+            remaining: amount,
+            reason: String::from_str(env, "unused credit restored"),
+            disposition: CreditDisposition::ApplyToNextInvoice,
+            status: CreditNoteStatus::Pending,
+            created_at: env.ledger().timestamp(),
+            refund_tx_id: None,
+        };
+        storage::set_credit_note(env, credit_note.id, &credit_note);
+
+        let asset = &invoice.denomination_asset;
+        let mut pending = storage::get_pending_credits(env, &invoice.creator, &client, asset);
+        pending.push_back(credit_note.id);
+        storage::set_pending_credits(env, &invoice.creator, &client, asset, &pending);
+    }
+
+    /// Consume pending credits between the invoice's creator and client in
+    /// the invoice's denomination asset, oldest first, up to the invoice
+    /// amount. Returns the credit applied.
+    ///
+    /// Credits are owed by the creator, so none are applied once the
+    /// receivable has been sold and the payment goes to another holder.
+    pub(crate) fn apply_pending_credits(env: &Env, invoice: &Invoice) -> i128 {
+        let Some(client) = invoice.client.clone() else {
+            return 0;
+        };
+        if invoice.receivable_holder != invoice.creator {
+            return 0;
+        }
+        let asset = &invoice.denomination_asset;
+        let pending = storage::get_pending_credits(env, &invoice.creator, &client, asset);
+        if pending.is_empty() {
+            return 0;
+        }
+
+        let mut applied: i128 = 0;
+        let mut still_pending = Vec::new(env);
+        for id in pending.iter() {
+            let Some(mut credit_note) = storage::get_credit_note(env, id) else {
+                continue;
+            };
+
+            let take = credit_note.remaining.min(invoice.amount - applied);
+            if take > 0 {
+                applied += take;
+                credit_note.remaining -= take;
+                if credit_note.remaining == 0 {
+                    credit_note.status = CreditNoteStatus::Applied;
+                }
+                storage::set_credit_note(env, id, &credit_note);
+            }
+
+            if credit_note.remaining > 0 {
+                still_pending.push_back(id);
+            }
+        }
+        storage::set_pending_credits(env, &invoice.creator, &client, asset, &still_pending);
+
+        applied
+    }
+}
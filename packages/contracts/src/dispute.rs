@@ -0,0 +1,184 @@
+//! Post-settlement dispute window (holdback) for executed invoices
+
+use soroban_sdk::{contractimpl, Address, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    HoldbackStatus, Invoice, InvoiceStatus,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Opt an invoice into a holdback window (called by creator)
+    ///
+    /// Once the invoice is executed, the paid funds stay in contract custody
+    /// for `window` seconds. The client can dispute during the window, in
+    /// which case `arbiter` decides who receives the funds; otherwise anyone
+    /// can release them to the receivable holder after the window ends.
+    pub fn set_invoice_holdback(
+        env: Env,
+        invoice_id: u64,
+        creator: Address,
+        window: u64,
+        arbiter: Address,
+    ) -> Result<Invoice, ContractError> {
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify creator authorization
+        validation::validate_signature(&env, &creator, &[], &[])?;
+        validation::validate_address(&env, &arbiter)?;
+
+        if invoice.creator != creator {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Neither party may decide its own dispute
+        if arbiter == invoice.creator || invoice.client.as_ref() == Some(&arbiter) {
+            return Err(ContractError::InvalidArbiter);
+        }
+
+        // Split invoices pay each share out directly
+        if (invoice.status != InvoiceStatus::Draft && invoice.status != InvoiceStatus::Sent)
+            || !invoice.shares.is_empty()
+        {
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        if window == 0 {
+            return Err(ContractError::InvalidSchedule);
+        }
+
+        invoice.holdback_status = HoldbackStatus::Configured;
+        invoice.holdback_window = window;
+        invoice.holdback_arbiter = Some(arbiter);
+        invoice.version += 1;
+        storage::set_invoice(&env, invoice_id, &invoice);
+
+        Ok(invoice)
+    }
+
+    /// Dispute an executed invoice while its holdback window is open (called by client)
+    pub fn raise_invoice_dispute(
+        env: Env,
+        invoice_id: u64,
+        client: Address,
+        reason: String,
+    ) -> Result<Invoice, ContractError> {
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify client authorization
+        validation::validate_signature(&env, &client, &[], &[])?;
+
+        if Self::invoice_client(&invoice)? != client {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if invoice.holdback_status != HoldbackStatus::Holding {
+            return Err(ContractError::HoldbackNotActive);
+        }
+        if Self::holdback_window_closed(&env, &invoice) {
+            return Err(ContractError::DisputeWindowClosed);
+        }
+
+        invoice.holdback_status = HoldbackStatus::Disputed;
+        invoice.dispute_reason = Some(reason.clone());
+        Self::save_invoice_transition(&env, &invoice, Some(client), Some(reason));
+
+        Ok(invoice)
+    }
+
+    /// Resolve a disputed invoice (called by arbiter)
+    ///
+    /// The held funds go to the receivable holder when `release_to_creator`
+    /// is true; otherwise they are returned to the client and the invoice
+    /// becomes `Refunded`.
+    pub fn resolve_invoice_dispute(
+        env: Env,
+        invoice_id: u64,
+        arbiter: Address,
+        release_to_creator: bool,
+    ) -> Result<Invoice, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify arbiter authorization
+        validation::validate_signature(&env, &arbiter, &[], &[])?;
+
+        if invoice.holdback_status != HoldbackStatus::Disputed {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::HoldbackNotActive);
+        }
+        if invoice.holdback_arbiter != Some(arbiter.clone()) {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::Unauthorized);
+        }
+
+        if release_to_creator {
+            invoice.holdback_status = HoldbackStatus::Released;
+            let client = Self::invoice_client(&invoice)?;
+            Self::pay_receivable_holder(&env, &mut invoice, client);
+        } else {
+            // Return the held funds to the client
+            // (in real implementation, transfer funds from contract to client)
+            // and give back any pending credit applied at execution
+            Self::restore_pending_credit(&env, &invoice, invoice.amount - invoice.paid_amount);
+            invoice.holdback_status = HoldbackStatus::Refunded;
+            invoice.status = InvoiceStatus::Refunded;
+            invoice.paid_amount = 0;
+            invoice.settled_amount = 0;
+            // Credit notes are blocked while funds are held, so nothing
+            // was credited against the refunded payment
+            invoice.credited_amount = 0;
+        }
+        let note = String::from_str(&env, "dispute resolved");
+        Self::save_invoice_transition(&env, &invoice, Some(arbiter), Some(note));
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(invoice)
+    }
+
+    /// Release held funds to the receivable holder once the window has
+    /// ended without a dispute (callable by anyone)
+    pub fn release_invoice_holdback(env: Env, invoice_id: u64) -> Result<Invoice, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        if invoice.holdback_status != HoldbackStatus::Holding {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::HoldbackNotActive);
+        }
+        if !Self::holdback_window_closed(&env, &invoice) {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::DisputeWindowOpen);
+        }
+
+        invoice.holdback_status = HoldbackStatus::Released;
+        let client = Self::invoice_client(&invoice)?;
+        Self::pay_receivable_holder(&env, &mut invoice, client);
+        let note = String::from_str(&env, "holdback released");
+        Self::save_invoice_transition(&env, &invoice, None, Some(note));
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(invoice)
+    }
+
+    /// Whether the dispute window of a held invoice has ended
+    fn holdback_window_closed(env: &Env, invoice: &Invoice) -> bool {
+        invoice
+            .holdback_release_at
+            .is_some_and(|release_at| env.ledger().timestamp() >= release_at)
+    }
+}
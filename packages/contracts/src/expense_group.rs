@@ -0,0 +1,180 @@
+//! Shared-expense groups settled with net P2P transfers
+
+use soroban_sdk::{contractimpl, Address, Env, Map, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    ExpenseGroup, TransactionLink, TransactionType,
+};
+use crate::netting::net_transfers;
+use crate::validation;
+use crate::storage;
+
+/// Maximum number of members in one expense group
+const MAX_GROUP_MEMBERS: u32 = 20;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Create an expense group (called by creator)
+    ///
+    /// The creator is always a member, whether or not `members` lists them.
+    pub fn create_expense_group(env: Env, creator: Address, members: Vec<Address>) -> Result<u64, ContractError> {
+        // Verify creator authorization
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        let mut group_members = Vec::new(&env);
+        group_members.push_back(creator.clone());
+        for member in members.iter() {
+            validation::validate_address(&env, &member)?;
+            if !group_members.contains(&member) {
+                group_members.push_back(member);
+            }
+        }
+        if group_members.len() < 2 || group_members.len() > MAX_GROUP_MEMBERS {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let group = ExpenseGroup {
+            id: storage::get_next_expense_group_id(&env),
+            creator,
+            members: group_members,
+            balances: Map::new(&env),
+            expense_count: 0,
+            settlement_count: 0,
+            created_at: env.ledger().timestamp(),
+        };
+        storage::set_expense_group(&env, group.id, &group);
+
+        Ok(group.id)
+    }
+
+    /// Record an expense `payer` covered for `participants` (called by payer)
+    ///
+    /// The amount is shared equally among the participants, who may include
+    /// the payer; the first participant also bears the rounding remainder.
+    pub fn record_group_expense(
+        env: Env,
+        group_id: u64,
+        payer: Address,
+        amount: i128,
+        participants: Vec<Address>,
+    ) -> Result<ExpenseGroup, ContractError> {
+        let mut group = storage::get_expense_group(&env, group_id)
+            .ok_or(ContractError::ExpenseGroupNotFound)?;
+
+        // Verify payer authorization
+        validation::validate_signature(&env, &payer, &[], &[])?;
+        validation::validate_amount(amount)?;
+
+        if !group.members.contains(&payer) {
+            return Err(ContractError::NotGroupMember);
+        }
+        if participants.is_empty() {
+            return Err(ContractError::InvalidAmount);
+        }
+        let mut seen: Vec<Address> = Vec::new(&env);
+        for participant in participants.iter() {
+            if !group.members.contains(&participant) {
+                return Err(ContractError::NotGroupMember);
+            }
+            if seen.contains(&participant) {
+                return Err(ContractError::InvalidAmount);
+            }
+            seen.push_back(participant);
+        }
+
+        // Credit the payer, then debit each participant's share
+        let share = amount / participants.len() as i128;
+        let remainder = amount - share * participants.len() as i128;
+        Self::adjust_group_balance(&mut group.balances, &payer, amount)?;
+        for (index, participant) in participants.iter().enumerate() {
+            let owed = if index == 0 { share + remainder } else { share };
+            Self::adjust_group_balance(&mut group.balances, &participant, -owed)?;
+        }
+
+        group.expense_count += 1;
+        storage::set_expense_group(&env, group_id, &group);
+
+        Ok(group)
+    }
+
+    /// Settle every outstanding balance in the group (callable by anyone)
+    ///
+    /// Matches members who owe against members who are owed greedily, which
+    /// needs at most one transfer fewer than the number of members with a
+    /// balance, and records each transfer as a P2P transaction. Every
+    /// member who owes must authorize their payment; if any cannot, nothing
+    /// is settled. Returns the transaction IDs.
+    pub fn settle_expense_group(env: Env, group_id: u64) -> Result<Vec<u64>, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::settle_group_balances(&env, group_id);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Get expense group details
+    pub fn get_expense_group(env: Env, group_id: u64) -> Result<ExpenseGroup, ContractError> {
+        storage::get_expense_group(&env, group_id)
+            .ok_or(ContractError::ExpenseGroupNotFound)
+    }
+
+    /// Add `delta` to a member's balance, dropping balances that reach zero
+    /// and rejecting any that overflow or cannot be negated
+    fn adjust_group_balance(
+        balances: &mut Map<Address, i128>,
+        member: &Address,
+        delta: i128,
+    ) -> Result<(), ContractError> {
+        let balance = balances
+            .get(member.clone())
+            .unwrap_or(0)
+            .checked_add(delta)
+            .filter(|balance| *balance != i128::MIN)
+            .ok_or(ContractError::InvalidAmount)?;
+        if balance == 0 {
+            balances.remove(member.clone());
+        } else {
+            balances.set(member.clone(), balance);
+        }
+        Ok(())
+    }
+
+    /// Record the net transfers that clear a group's balances
+    fn settle_group_balances(env: &Env, group_id: u64) -> Result<Vec<u64>, ContractError> {
+        let mut group = storage::get_expense_group(env, group_id)
+            .ok_or(ContractError::ExpenseGroupNotFound)?;
+
+        // Members who owe authorize their payment once
+        for (member, balance) in group.balances.iter() {
+            if balance < 0 {
+                validation::validate_balance(env, &member, -balance)?;
+            }
+        }
+
+        // Execute each transfer
+        // (in real implementation, transfer funds between the members)
+        let mut transaction_ids = Vec::new(env);
+        for transfer in net_transfers(env, &group.balances).iter() {
+            let transaction = Self::record_transaction(
+                env,
+                TransactionType::P2P,
+                transfer.from,
+                transfer.to,
+                transfer.amount,
+                String::from_str(env, "group settlement"),
+                TransactionLink::ExpenseGroup(group_id),
+            );
+            transaction_ids.push_back(transaction.id);
+        }
+
+        group.balances = Map::new(env);
+        group.settlement_count += 1;
+        storage::set_expense_group(env, group_id, &group);
+
+        Ok(transaction_ids)
+    }
+}
@@ -0,0 +1,238 @@
+//! Registry of unique handles for paying by username
+
+use soroban_sdk::{contractimpl, Address, Env, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    HandlePayoutEntry, HandleRecord, PayoutEntry, TransactionLink, TransactionResult,
+};
+use crate::payout::MAX_BATCH_PAYOUT;
+use crate::validation;
+use crate::storage;
+
+/// Minimum handle length in bytes
+const MIN_HANDLE_LEN: u32 = 3;
+
+/// Maximum handle length in bytes
+const MAX_HANDLE_LEN: u32 = 32;
+
+/// Normalize a handle to lowercase, rejecting anything but ASCII letters,
+/// digits and underscores
+fn normalize_handle(env: &Env, handle: &String) -> Result<String, ContractError> {
+    let len = handle.len();
+    if !(MIN_HANDLE_LEN..=MAX_HANDLE_LEN).contains(&len) {
+        return Err(ContractError::InvalidHandle);
+    }
+
+    let mut buf = [0u8; MAX_HANDLE_LEN as usize];
+    let bytes = &mut buf[..len as usize];
+    handle.copy_into_slice(bytes);
+    for byte in bytes.iter_mut() {
+        if !(byte.is_ascii_alphanumeric() || *byte == b'_') {
+            return Err(ContractError::InvalidHandle);
+        }
+        byte.make_ascii_lowercase();
+    }
+    Ok(String::from_bytes(env, bytes))
+}
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Claim a handle pointing at the owner's address (called by owner)
+    ///
+    /// Handles are case-insensitive: `Alice` and `alice` are the same
+    /// handle. Returns the normalized handle.
+    pub fn register_handle(env: Env, owner: Address, handle: String) -> Result<String, ContractError> {
+        // Verify owner authorization
+        validation::validate_signature(&env, &owner, &[], &[])?;
+
+        let handle = normalize_handle(&env, &handle)?;
+        if storage::get_handle(&env, &handle).is_some() {
+            return Err(ContractError::HandleTaken);
+        }
+
+        let now = env.ledger().timestamp();
+        let record = HandleRecord {
+            handle: handle.clone(),
+            owner: owner.clone(),
+            target: owner,
+            registered_at: now,
+            updated_at: now,
+        };
+        storage::set_handle(&env, &handle, &record);
+
+        Ok(handle)
+    }
+
+    /// Point a handle at a different address (called by owner)
+    pub fn update_handle(
+        env: Env,
+        handle: String,
+        owner: Address,
+        target: Address,
+    ) -> Result<HandleRecord, ContractError> {
+        let mut record = Self::owned_handle(&env, &handle, &owner)?;
+        validation::validate_address(&env, &target)?;
+
+        record.target = target;
+        record.updated_at = env.ledger().timestamp();
+        storage::set_handle(&env, &record.handle, &record);
+
+        Ok(record)
+    }
+
+    /// Release a handle so anyone can claim it again (called by owner)
+    pub fn release_handle(env: Env, handle: String, owner: Address) -> Result<(), ContractError> {
+        let record = Self::owned_handle(&env, &handle, &owner)?;
+        storage::remove_handle(&env, &record.handle);
+        Ok(())
+    }
+
+    /// Get handle details
+    pub fn get_handle(env: Env, handle: String) -> Result<HandleRecord, ContractError> {
+        let handle = normalize_handle(&env, &handle)?;
+        storage::get_handle(&env, &handle)
+            .ok_or(ContractError::HandleNotFound)
+    }
+
+    /// Resolve a handle to the address that receives payments to it
+    pub fn resolve_handle(env: Env, handle: String) -> Result<Address, ContractError> {
+        Self::get_handle(env, handle).map(|record| record.target)
+    }
+
+    /// Execute a P2P transaction to the address a handle currently resolves to
+    ///
+    /// The transaction records the normalized handle it was sent to.
+    pub fn execute_p2p_to_handle(
+        env: Env,
+        sender: Address,
+        handle: String,
+        amount: i128,
+        memo: String,
+    ) -> Result<TransactionResult, ContractError> {
+        let record = Self::get_handle(env.clone(), handle)?;
+        Self::transfer_p2p(&env, sender, record.target, amount, memo, TransactionLink::Handle(record.handle))
+    }
+
+    /// Send a pending P2P transfer to the address a handle currently
+    /// resolves to
+    ///
+    /// The transfer stays with the resolved address even if the handle is
+    /// later repointed. The transaction records the normalized handle.
+    pub fn send_pending_p2p_to_handle(
+        env: Env,
+        sender: Address,
+        handle: String,
+        amount: i128,
+        memo: String,
+        expires_at: u64,
+    ) -> Result<TransactionResult, ContractError> {
+        let record = Self::get_handle(env.clone(), handle)?;
+        Self::hold_p2p_transfer(
+            &env,
+            sender,
+            record.target,
+            amount,
+            memo,
+            expires_at,
+            TransactionLink::Handle(record.handle),
+        )
+    }
+
+    /// Schedule a payment to whatever address a handle resolves to when the
+    /// payment executes (called by sender)
+    ///
+    /// The payment records the normalized handle and its owner; the handle
+    /// must still belong to that owner at execution.
+    pub fn schedule_payment_to_handle(
+        env: Env,
+        sender: Address,
+        handle: String,
+        amount: i128,
+        memo: String,
+        execute_at: u64,
+    ) -> Result<u64, ContractError> {
+        let record = Self::get_handle(env.clone(), handle)?;
+        Self::lock_scheduled_payment(&env, sender, record.target.clone(), Some(record), amount, memo, execute_at)
+    }
+
+    /// Ask whoever a handle currently resolves to for money (called by payee)
+    ///
+    /// The request is addressed to the resolved address and records the
+    /// normalized handle.
+    pub fn create_payment_request_to_handle(
+        env: Env,
+        payee: Address,
+        handle: String,
+        amount: i128,
+        memo: String,
+    ) -> Result<u64, ContractError> {
+        let record = Self::get_handle(env.clone(), handle)?;
+        Self::open_payment_request(&env, payee, record.target, Some(record.handle), amount, memo)
+    }
+
+    /// Pay many handles from one sender in a single call
+    ///
+    /// Each handle is resolved when the batch executes, and each leg's
+    /// transaction records the normalized handle it was sent to.
+    pub fn execute_batch_payout_to_handles(
+        env: Env,
+        sender: Address,
+        entries: Vec<HandlePayoutEntry>,
+    ) -> Result<Vec<u64>, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        if entries.is_empty() || entries.len() > MAX_BATCH_PAYOUT {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut payouts = Vec::new(&env);
+        let mut links = Vec::new(&env);
+        for entry in entries.iter() {
+            let record = match Self::get_handle(env.clone(), entry.handle) {
+                Ok(record) => record,
+                Err(err) => {
+                    validation::clear_reentrancy(&env);
+                    return Err(err);
+                }
+            };
+            payouts.push_back(PayoutEntry {
+                recipient: record.target,
+                amount: entry.amount,
+                memo: entry.memo,
+            });
+            links.push_back(TransactionLink::Handle(record.handle));
+        }
+        let result = Self::pay_out_batch(&env, sender, payouts, links);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Resolve a stored handle, failing if it has since changed owner
+    pub(crate) fn resolve_handle_of(env: &Env, handle: String, owner: &Address) -> Result<Address, ContractError> {
+        match storage::get_handle(env, &handle) {
+            Some(record) if record.owner == *owner => Ok(record.target),
+            _ => Err(ContractError::HandleNotFound),
+        }
+    }
+
+    /// Get a handle record owned by `owner`, verifying their authorization
+    fn owned_handle(env: &Env, handle: &String, owner: &Address) -> Result<HandleRecord, ContractError> {
+        let handle = normalize_handle(env, handle)?;
+        let record = storage::get_handle(env, &handle)
+            .ok_or(ContractError::HandleNotFound)?;
+
+        if record.owner != *owner {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Verify owner authorization
+        validation::validate_signature(env, owner, &[], &[])?;
+
+        Ok(record)
+    }
+}
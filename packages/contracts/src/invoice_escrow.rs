@@ -0,0 +1,138 @@
+//! Pay-on-delivery: funding approved invoices through escrow
+
+use soroban_sdk::{contractimpl, Address, Env, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    Condition, EscrowContract, EscrowResult, EscrowStatus, HoldbackStatus, InvoiceStatus,
+    TransactionLink, TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Fund an approved invoice into an escrow instead of paying it directly
+    /// (called by client)
+    ///
+    /// The escrow holds the invoice amount, less any pending credit notes,
+    /// for the current receivable holder (the creator unless the receivable
+    /// was sold) under the client's conditions. Releasing the escrow
+    /// executes the invoice; refunding it closes the invoice as expired and
+    /// restores the applied credit. An invoice fully covered by pending
+    /// credits has nothing to escrow and is rejected with `InvalidAmount`;
+    /// settle it with `execute_invoice` instead.
+    pub fn fund_invoice_escrow(
+        env: Env,
+        invoice_id: u64,
+        client: Address,
+        conditions: Vec<Condition>,
+        expires_at: u64,
+    ) -> Result<EscrowResult, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify client matches invoice
+        if Self::invoice_client(&invoice)? != client {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Oracle-priced invoices are converted at execution, not escrowed up
+        // front, and escrow releases would bypass a configured holdback
+        if invoice.status != InvoiceStatus::Approved
+            || invoice.denomination_asset.is_some()
+            || invoice.holdback_status != HoldbackStatus::None
+        {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        if env.ledger().timestamp() > invoice.due_date {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvoiceExpired);
+        }
+
+        // Validate expiration time is in the future
+        if expires_at <= env.ledger().timestamp() {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Apply credit notes pending for this client before locking funds.
+        // A fully credited invoice has nothing to escrow; rejecting it rolls
+        // the credit back so execute_invoice can apply it instead.
+        let credit = Self::apply_pending_credits(&env, &invoice);
+        let amount_due = invoice.amount - credit;
+        if amount_due <= 0 {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Validate balance and authorization
+        validation::validate_balance(&env, &client, amount_due)?;
+
+        let escrow = Self::store_new_escrow(
+            &env,
+            client.clone(),
+            invoice.receivable_holder.clone(),
+            amount_due,
+            conditions,
+            expires_at,
+            Some(invoice_id),
+        );
+
+        invoice.status = InvoiceStatus::Escrowed;
+        invoice.escrow_id = Some(escrow.id);
+        Self::save_invoice_transition(&env, &invoice, Some(client), None);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(EscrowResult {
+            escrow_id: escrow.id,
+            status: EscrowStatus::Active,
+            tx_hash: None,
+        })
+    }
+
+    /// Settle the invoice funded by an escrow once the escrow is released or
+    /// refunded. Escrows without an invoice are ignored.
+    pub(crate) fn settle_escrowed_invoice(env: &Env, escrow: &EscrowContract) {
+        let Some(mut invoice) = escrow.invoice_id.and_then(|id| storage::get_invoice(env, id)) else {
+            return;
+        };
+        if invoice.status != InvoiceStatus::Escrowed {
+            return;
+        }
+
+        match escrow.status {
+            EscrowStatus::Released => {
+                let settlement = Self::record_transaction(
+                    env,
+                    TransactionType::Escrow,
+                    escrow.sender.clone(),
+                    escrow.recipient.clone(),
+                    escrow.amount,
+                    invoice.description.clone(),
+                    TransactionLink::None,
+                );
+                invoice.settlement_tx_id = Some(settlement.id);
+                invoice.paid_amount = escrow.amount;
+                invoice.settled_amount = escrow.amount;
+                invoice.status = InvoiceStatus::Executed;
+                Self::save_invoice_transition(env, &invoice, None, None);
+            }
+            EscrowStatus::Refunded => {
+                // Credit applied at funding was never used
+                Self::restore_pending_credit(env, &invoice, invoice.amount - escrow.amount);
+                invoice.status = InvoiceStatus::Expired;
+                let note = String::from_str(env, "escrow refunded");
+                Self::save_invoice_transition(env, &invoice, None, Some(note));
+            }
+            _ => {}
+        }
+    }
+}
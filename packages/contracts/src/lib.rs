@@ -14,7 +14,35 @@ pub mod types;
 pub mod validation;
 pub mod storage;
 
+mod recurring;
+mod credit;
+mod approval;
+mod claimable;
+mod receivable;
+mod invoice_escrow;
+mod dispute;
+mod split;
+mod batch;
+mod pricing;
+mod numbering;
+mod payment_request;
+mod netting;
+mod payout;
+mod payment_split;
+mod stream;
+mod vesting;
+mod scheduled;
+mod pending_transfer;
+mod payment_link;
+mod expense_group;
+mod refund;
+mod handle;
+
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
 pub use types::*;
+pub use pricing::{PriceOracle, PriceOracleClient};
 
 #[contract]
 pub struct StellarDAppContract;
@@ -64,6 +92,7 @@ impl StellarDAppContract {
             status: TransactionStatus::Pending,
             timestamp: env.ledger().timestamp(),
             metadata: metadata.clone(),
+            link: TransactionLink::None,
         };
 
         // Store transaction
@@ -105,20 +134,32 @@ impl StellarDAppContract {
         recipient: Address,
         amount: i128,
         memo: String,
+    ) -> Result<TransactionResult, ContractError> {
+        Self::transfer_p2p(&env, sender, recipient, amount, memo, TransactionLink::None)
+    }
+
+    /// Validate and record a P2P transfer, linking it to the record it settles
+    fn transfer_p2p(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        memo: String,
+        link: TransactionLink,
     ) -> Result<TransactionResult, ContractError> {
         // Check for reentrancy
-        validation::check_reentrancy(&env)?;
+        validation::check_reentrancy(env)?;
         
         // Validate inputs
-        validation::validate_address(&env, &sender)?;
-        validation::validate_address(&env, &recipient)?;
+        validation::validate_address(env, &sender)?;
+        validation::validate_address(env, &recipient)?;
         validation::validate_amount(amount)?;
         
         // Validate balance and authorization
-        validation::validate_balance(&env, &sender, amount)?;
+        validation::validate_balance(env, &sender, amount)?;
 
         // Create transaction record
-        let tx_id = storage::get_next_transaction_id(&env);
+        let tx_id = storage::get_next_transaction_id(env);
         let transaction = Transaction {
             id: tx_id,
             transaction_type: TransactionType::P2P,
@@ -128,26 +169,52 @@ impl StellarDAppContract {
             status: TransactionStatus::Pending,
             timestamp: env.ledger().timestamp(),
             metadata: memo,
+            link,
         };
 
         // Store transaction
-        storage::set_transaction(&env, tx_id, &transaction);
+        storage::set_transaction(env, tx_id, &transaction);
 
         // Execute the P2P transfer
         let mut updated_transaction = transaction;
         updated_transaction.status = TransactionStatus::Confirmed;
-        storage::set_transaction(&env, tx_id, &updated_transaction);
+        storage::set_transaction(env, tx_id, &updated_transaction);
 
         // Clear reentrancy guard
-        validation::clear_reentrancy(&env);
+        validation::clear_reentrancy(env);
 
         Ok(TransactionResult {
             transaction_id: tx_id,
             status: TransactionStatus::Confirmed,
-            tx_hash: String::from_str(&env, "p2p_tx_hash"),
+            tx_hash: String::from_str(env, "p2p_tx_hash"),
         })
     }
 
+    /// Store a confirmed transaction record with the next transaction ID
+    fn record_transaction(
+        env: &Env,
+        transaction_type: TransactionType,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        metadata: String,
+        link: TransactionLink,
+    ) -> Transaction {
+        let transaction = Transaction {
+            id: storage::get_next_transaction_id(env),
+            transaction_type,
+            sender,
+            recipient,
+            amount,
+            status: TransactionStatus::Confirmed,
+            timestamp: env.ledger().timestamp(),
+            metadata,
+            link,
+        };
+        storage::set_transaction(env, transaction.id, &transaction);
+        transaction
+    }
+
     // ========== Escrow Functions ==========
     
     /// Create a new escrow contract
@@ -174,21 +241,17 @@ impl StellarDAppContract {
             return Err(ContractError::InvalidAmount);
         }
 
-        // Create escrow record
-        let escrow_id = storage::get_next_escrow_id(&env);
-        let escrow = EscrowContract {
-            id: escrow_id,
-            sender: sender.clone(),
+        // Create escrow record and lock funds
+        let escrow = Self::store_new_escrow(
+            &env,
+            sender,
             recipient,
             amount,
             conditions,
-            status: EscrowStatus::Active,
-            created_at: env.ledger().timestamp(),
             expires_at,
-        };
-
-        // Lock funds (in real implementation, this would transfer funds to contract)
-        storage::set_escrow(&env, escrow_id, &escrow);
+            None,
+        );
+        let escrow_id = escrow.id;
 
         // Clear reentrancy guard
         validation::clear_reentrancy(&env);
@@ -200,6 +263,33 @@ impl StellarDAppContract {
         })
     }
 
+    /// Build and store a new active escrow record with the next escrow ID
+    fn store_new_escrow(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        conditions: Vec<Condition>,
+        expires_at: u64,
+        invoice_id: Option<u64>,
+    ) -> EscrowContract {
+        let escrow = EscrowContract {
+            id: storage::get_next_escrow_id(env),
+            sender,
+            recipient,
+            amount,
+            conditions,
+            status: EscrowStatus::Active,
+            created_at: env.ledger().timestamp(),
+            expires_at,
+            invoice_id,
+        };
+
+        // Lock funds (in real implementation, this would transfer funds to contract)
+        storage::set_escrow(env, escrow.id, &escrow);
+        escrow
+    }
+
     /// Check if escrow conditions are met
     pub fn check_escrow_conditions(env: Env, escrow_id: u64) -> Result<bool, ContractError> {
         let escrow = storage::get_escrow(&env, escrow_id)
@@ -276,6 +366,7 @@ impl StellarDAppContract {
         // Release funds (in real implementation, transfer to recipient)
         escrow.status = EscrowStatus::Released;
         storage::set_escrow(&env, escrow_id, &escrow);
+        Self::settle_escrowed_invoice(&env, &escrow);
 
         // Clear reentrancy guard
         validation::clear_reentrancy(&env);
@@ -309,6 +400,7 @@ impl StellarDAppContract {
         // Refund funds (in real implementation, transfer back to sender)
         escrow.status = EscrowStatus::Refunded;
         storage::set_escrow(&env, escrow_id, &escrow);
+        Self::settle_escrowed_invoice(&env, &escrow);
 
         // Clear reentrancy guard
         validation::clear_reentrancy(&env);
@@ -363,53 +455,208 @@ impl StellarDAppContract {
         amount: i128,
         description: String,
         due_date: u64,
+        external_ref: Option<String>,
     ) -> Result<InvoiceResult, ContractError> {
+        // Verify creator authorization before allocating a number or reference
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
         // Check for reentrancy
         validation::check_reentrancy(&env)?;
-        
+
+        // Create and store invoice record
+        let invoice_id = match Self::create_draft_invoice(
+            &env,
+            creator,
+            client,
+            amount,
+            description,
+            due_date,
+            external_ref,
+        ) {
+            Ok(invoice_id) => invoice_id,
+            Err(err) => {
+                validation::clear_reentrancy(&env);
+                return Err(err);
+            }
+        };
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(InvoiceResult {
+            invoice_id,
+            status: InvoiceStatus::Draft,
+            tx_hash: None,
+        })
+    }
+
+    /// Validate and store a new draft invoice, returning its ID
+    ///
+    /// The caller must already have verified the creator's authorization.
+    fn create_draft_invoice(
+        env: &Env,
+        creator: Address,
+        client: Address,
+        amount: i128,
+        description: String,
+        due_date: u64,
+        external_ref: Option<String>,
+    ) -> Result<u64, ContractError> {
         // Validate inputs
-        validation::validate_address(&env, &creator)?;
-        validation::validate_address(&env, &client)?;
+        validation::validate_address(env, &creator)?;
+        validation::validate_address(env, &client)?;
         validation::validate_amount(amount)?;
 
         // Validate due date is in the future
         if due_date <= env.ledger().timestamp() {
-            validation::clear_reentrancy(&env);
             return Err(ContractError::InvalidAmount);
         }
 
-        // Create invoice record
-        let invoice_id = storage::get_next_invoice_id(&env);
-        let invoice = Invoice {
-            id: invoice_id,
+        let mut invoice = Self::build_invoice(
+            env,
+            creator.clone(),
+            Some(client),
+            amount,
+            description,
+            due_date,
+            InvoiceStatus::Draft,
+        );
+        if let Some(external_ref) = external_ref {
+            Self::reserve_external_ref(env, &invoice, &external_ref)?;
+            invoice.external_ref = Some(external_ref);
+        }
+        Self::save_invoice_transition(env, &invoice, Some(creator), None);
+        Ok(invoice.id)
+    }
+
+    /// Build a new invoice record with the next invoice ID and the creator's
+    /// next invoice number
+    ///
+    /// The caller stores it with `save_invoice_transition` once any optional
+    /// fields are set, so the creation is recorded in the status history.
+    fn build_invoice(
+        env: &Env,
+        creator: Address,
+        client: Option<Address>,
+        amount: i128,
+        description: String,
+        due_date: u64,
+        status: InvoiceStatus,
+    ) -> Invoice {
+        let now = env.ledger().timestamp();
+        let approved_at = if status == InvoiceStatus::Approved {
+            Some(now)
+        } else {
+            None
+        };
+        let id = storage::get_next_invoice_id(env);
+        let creator_number = storage::get_next_creator_invoice_number(env, &creator);
+        storage::set_creator_invoice(env, &creator, creator_number, id);
+        let number_prefix = storage::get_invoice_prefix(env, &creator);
+
+        Invoice {
+            id,
             creator: creator.clone(),
             client,
             amount,
             description,
-            status: InvoiceStatus::Draft,
-            created_at: env.ledger().timestamp(),
+            status,
+            created_at: now,
             due_date,
-            approved_at: None,
-        };
+            approved_at,
+            recurring_id: None,
+            version: 1,
+            paid_amount: 0,
+            credited_amount: 0,
+            claim_key: None,
+            receivable_holder: creator,
+            settlement_tx_id: None,
+            escrow_id: None,
+            holdback_status: HoldbackStatus::None,
+            holdback_window: 0,
+            holdback_arbiter: None,
+            holdback_release_at: None,
+            dispute_reason: None,
+            shares: Vec::new(env),
+            denomination_asset: None,
+            settlement_asset: None,
+            max_price_age: 0,
+            max_slippage_bps: 0,
+            quoted_rate: 0,
+            settled_amount: 0,
+            creator_number,
+            number_prefix,
+            external_ref: None,
+            netting_batch: None,
+        }
+    }
 
-        // Store invoice
-        storage::set_invoice(&env, invoice_id, &invoice);
+    /// Record payment of an executed invoice to its current receivable holder
+    /// (in real implementation, transfer funds from payer to holder)
+    fn pay_receivable_holder(env: &Env, invoice: &mut Invoice, payer: Address) {
+        if invoice.settled_amount <= 0 {
+            return;
+        }
+        let settlement = Self::record_transaction(
+            env,
+            TransactionType::Invoice,
+            payer,
+            invoice.receivable_holder.clone(),
+            invoice.settled_amount,
+            invoice.description.clone(),
+            TransactionLink::None,
+        );
+        invoice.settlement_tx_id = Some(settlement.id);
+    }
 
-        // Clear reentrancy guard
-        validation::clear_reentrancy(&env);
+    /// Get the invoice client, failing if no client has claimed it yet
+    fn invoice_client(invoice: &Invoice) -> Result<Address, ContractError> {
+        invoice.client.clone().ok_or(ContractError::InvoiceUnclaimed)
+    }
 
-        Ok(InvoiceResult {
-            invoice_id,
-            status: InvoiceStatus::Draft,
-            tx_hash: None,
-        })
+    /// Store an invoice and append its current status to the status history
+    fn save_invoice_transition(
+        env: &Env,
+        invoice: &Invoice,
+        actor: Option<Address>,
+        note: Option<String>,
+    ) {
+        storage::set_invoice(env, invoice.id, invoice);
+        storage::append_invoice_history(
+            env,
+            invoice.id,
+            &InvoiceStatusEntry {
+                status: invoice.status.clone(),
+                actor,
+                timestamp: env.ledger().timestamp(),
+                note,
+            },
+        );
     }
 
-    /// Approve an invoice (called by client)
+    /// Approve an invoice (called by client or one of its approvers)
+    ///
+    /// `version` must match the current invoice version so the client always
+    /// approves exactly the terms they reviewed. If the client has an approval
+    /// policy, the invoice stays `Sent` until the policy is satisfied.
+    /// Oracle-priced invoices must be approved with `approve_invoice_with_quote`.
     pub fn approve_invoice(
         env: Env,
         invoice_id: u64,
-        client: Address,
+        approver: Address,
+        version: u32,
+    ) -> Result<InvoiceResult, ContractError> {
+        Self::approve_invoice_version(env, invoice_id, approver, version, None)
+    }
+
+    /// Approve an invoice version, quoting the oracle rate with the given
+    /// slippage bound once the approval policy is satisfied
+    fn approve_invoice_version(
+        env: Env,
+        invoice_id: u64,
+        approver: Address,
+        version: u32,
+        max_slippage_bps: Option<u32>,
     ) -> Result<InvoiceResult, ContractError> {
         // Check for reentrancy
         validation::check_reentrancy(&env)?;
@@ -417,11 +664,17 @@ impl StellarDAppContract {
         let mut invoice = storage::get_invoice(&env, invoice_id)
             .ok_or(ContractError::InvoiceNotFound)?;
 
-        // Verify client authorization (signature verification)
-        validation::validate_signature(&env, &client, &[], &[])?;
+        // Verify approver authorization (signature verification)
+        validation::validate_signature(&env, &approver, &[], &[])?;
+
+        // Invoices addressed to a secret must be claimed first
+        if invoice.client.is_none() {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvoiceUnclaimed);
+        }
         
-        // Verify client matches invoice
-        if invoice.client != client {
+        // Verify approver acts for the invoice client
+        if !Self::is_invoice_approver(&env, &invoice, &approver) {
             validation::clear_reentrancy(&env);
             return Err(ContractError::Unauthorized);
         }
@@ -432,26 +685,54 @@ impl StellarDAppContract {
             return Err(ContractError::InvoiceAlreadyApproved);
         }
 
+        // Check the client reviewed the current version
+        if invoice.version != version {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvoiceVersionMismatch);
+        }
+
+        // Oracle-priced invoices need the client to agree a quote
+        if invoice.denomination_asset.is_some() && max_slippage_bps.is_none() {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::PriceUnavailable);
+        }
+
         // Check if expired
         if env.ledger().timestamp() > invoice.due_date {
             // Mark as expired
             invoice.status = InvoiceStatus::Expired;
-            storage::set_invoice(&env, invoice_id, &invoice);
+            Self::save_invoice_transition(&env, &invoice, None, None);
             validation::clear_reentrancy(&env);
             return Err(ContractError::InvoiceExpired);
         }
 
-        // Approve invoice
-        invoice.status = InvoiceStatus::Approved;
-        invoice.approved_at = Some(env.ledger().timestamp());
-        storage::set_invoice(&env, invoice_id, &invoice);
+        // Record the approval and approve once the policy is satisfied
+        if Self::record_invoice_approval(&env, &invoice, &approver)? {
+            // Only the final approval fixes the quote the invoice settles against
+            if let Some(max_slippage_bps) = max_slippage_bps {
+                match Self::oracle_rate(&env, &invoice) {
+                    Ok(rate) => invoice.quoted_rate = rate,
+                    Err(err) => {
+                        validation::clear_reentrancy(&env);
+                        return Err(err);
+                    }
+                }
+                invoice.max_slippage_bps = max_slippage_bps;
+            }
+            invoice.status = InvoiceStatus::Approved;
+            invoice.approved_at = Some(env.ledger().timestamp());
+            Self::save_invoice_transition(&env, &invoice, Some(approver), None);
+        } else {
+            let note = String::from_str(&env, "partial approval");
+            Self::save_invoice_transition(&env, &invoice, Some(approver), Some(note));
+        }
 
         // Clear reentrancy guard
         validation::clear_reentrancy(&env);
 
         Ok(InvoiceResult {
             invoice_id,
-            status: InvoiceStatus::Approved,
+            status: invoice.status,
             tx_hash: None,
         })
     }
@@ -473,17 +754,43 @@ impl StellarDAppContract {
         // Check if expired
         if env.ledger().timestamp() > invoice.due_date {
             invoice.status = InvoiceStatus::Expired;
-            storage::set_invoice(&env, invoice_id, &invoice);
+            Self::save_invoice_transition(&env, &invoice, None, None);
             validation::clear_reentrancy(&env);
             return Err(ContractError::InvoiceExpired);
         }
 
-        // Validate client has sufficient balance
-        validation::validate_balance(&env, &invoice.client, invoice.amount)?;
+        // Apply credit notes pending for this client before charging
+        let credit = Self::apply_pending_credits(&env, &invoice);
+        let amount_due = invoice.amount - credit;
+
+        // Convert the amount due into the settlement asset for oracle-priced invoices
+        let settled_amount = match Self::convert_invoice_amount(&env, &invoice, amount_due) {
+            Ok(settled_amount) => settled_amount,
+            Err(err) => {
+                validation::clear_reentrancy(&env);
+                return Err(err);
+            }
+        };
+
+        // Validate client has sufficient balance. Subscription invoices were
+        // pre-authorized by the client through authorize_recurring_payment.
+        let client = Self::invoice_client(&invoice)?;
+        if settled_amount > 0 && !Self::is_pull_authorized(&env, &invoice) {
+            validation::validate_balance(&env, &client, settled_amount)?;
+        }
 
-        // Execute payment (in real implementation, transfer funds from client to creator)
+        // Execute payment. With a holdback the funds stay in contract custody
+        // until the dispute window ends.
         invoice.status = InvoiceStatus::Executed;
-        storage::set_invoice(&env, invoice_id, &invoice);
+        invoice.paid_amount = amount_due;
+        invoice.settled_amount = settled_amount;
+        if invoice.holdback_status == HoldbackStatus::Configured {
+            invoice.holdback_status = HoldbackStatus::Holding;
+            invoice.holdback_release_at = Some(env.ledger().timestamp().saturating_add(invoice.holdback_window));
+        } else {
+            Self::pay_receivable_holder(&env, &mut invoice, client);
+        }
+        Self::save_invoice_transition(&env, &invoice, None, None);
 
         // Clear reentrancy guard
         validation::clear_reentrancy(&env);
@@ -500,7 +807,7 @@ impl StellarDAppContract {
         env: Env,
         invoice_id: u64,
         client: Address,
-        _reason: String,
+        reason: String,
     ) -> Result<InvoiceResult, ContractError> {
         // Check for reentrancy
         validation::check_reentrancy(&env)?;
@@ -511,8 +818,14 @@ impl StellarDAppContract {
         // Verify client authorization
         validation::validate_signature(&env, &client, &[], &[])?;
         
-        // Verify client matches invoice
-        if invoice.client != client {
+        // Invoices addressed to a secret must be claimed first
+        if invoice.client.is_none() {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvoiceUnclaimed);
+        }
+
+        // Verify client or one of its approvers is rejecting
+        if invoice.client != Some(client.clone()) && !Self::is_invoice_approver(&env, &invoice, &client) {
             validation::clear_reentrancy(&env);
             return Err(ContractError::Unauthorized);
         }
@@ -525,7 +838,7 @@ impl StellarDAppContract {
 
         // Reject invoice
         invoice.status = InvoiceStatus::Rejected;
-        storage::set_invoice(&env, invoice_id, &invoice);
+        Self::save_invoice_transition(&env, &invoice, Some(client), Some(reason));
 
         // Clear reentrancy guard
         validation::clear_reentrancy(&env);
@@ -543,7 +856,7 @@ impl StellarDAppContract {
             .ok_or(ContractError::InvoiceNotFound)?;
 
         // Only check if invoice is in a state that can expire
-        if invoice.status != InvoiceStatus::Sent && invoice.status != InvoiceStatus::Approved {
+        if !Self::is_expirable(&invoice) {
             return Ok(InvoiceResult {
                 invoice_id,
                 status: invoice.status,
@@ -553,12 +866,12 @@ impl StellarDAppContract {
 
         // Check if expired
         if env.ledger().timestamp() > invoice.due_date {
-            invoice.status = InvoiceStatus::Expired;
-            storage::set_invoice(&env, invoice_id, &invoice);
+            invoice.status = Self::lapsed_status(&invoice);
+            Self::save_invoice_transition(&env, &invoice, None, None);
             
             return Ok(InvoiceResult {
                 invoice_id,
-                status: InvoiceStatus::Expired,
+                status: invoice.status,
                 tx_hash: None,
             });
         }
@@ -577,12 +890,22 @@ impl StellarDAppContract {
             .ok_or(ContractError::InvoiceNotFound)
     }
 
+    /// Get the status history of an invoice, oldest entry first
+    pub fn get_invoice_history(env: Env, invoice_id: u64) -> Result<Vec<InvoiceStatusEntry>, ContractError> {
+        if storage::get_invoice(&env, invoice_id).is_none() {
+            return Err(ContractError::InvoiceNotFound);
+        }
+        Ok(storage::get_invoice_history(&env, invoice_id))
+    }
+
     /// Mark invoice as sent (called after sending to client)
     pub fn mark_invoice_sent(env: Env, invoice_id: u64, creator: Address) -> Result<InvoiceResult, ContractError> {
         let mut invoice = storage::get_invoice(&env, invoice_id)
             .ok_or(ContractError::InvoiceNotFound)?;
 
         // Verify creator authorization
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
         if invoice.creator != creator {
             return Err(ContractError::Unauthorized);
         }
@@ -592,7 +915,7 @@ impl StellarDAppContract {
         }
 
         invoice.status = InvoiceStatus::Sent;
-        storage::set_invoice(&env, invoice_id, &invoice);
+        Self::save_invoice_transition(&env, &invoice, Some(creator), None);
 
         Ok(InvoiceResult {
             invoice_id,
@@ -600,6 +923,121 @@ impl StellarDAppContract {
             tx_hash: None,
         })
     }
+
+    /// Amend an unpaid invoice (called by creator)
+    ///
+    /// Bumps the invoice version. An approved invoice returns to `Sent`, so
+    /// the earlier approval no longer applies and the client must approve
+    /// the new version.
+    pub fn amend_invoice(
+        env: Env,
+        invoice_id: u64,
+        creator: Address,
+        amount: i128,
+        description: String,
+        due_date: u64,
+    ) -> Result<InvoiceResult, ContractError> {
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify creator authorization
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        // Once the receivable is sold, its terms belong to the buyer too
+        if invoice.creator != creator || invoice.receivable_holder != creator {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Split invoices are fixed by their payer shares
+        if !Self::is_unpaid(&invoice) || !invoice.shares.is_empty() {
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        // Validate new terms
+        validation::validate_amount(amount)?;
+        if due_date <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        invoice.amount = amount;
+        invoice.description = description;
+        invoice.due_date = due_date;
+        invoice.version += 1;
+        if invoice.status == InvoiceStatus::Approved {
+            invoice.status = InvoiceStatus::Sent;
+            invoice.approved_at = None;
+        }
+        // Any agreed quote applied to the old terms
+        invoice.quoted_rate = 0;
+        invoice.max_slippage_bps = 0;
+        let note = String::from_str(&env, "amended");
+        Self::save_invoice_transition(&env, &invoice, Some(creator), Some(note));
+
+        Ok(InvoiceResult {
+            invoice_id,
+            status: invoice.status,
+            tx_hash: None,
+        })
+    }
+
+    /// Cancel an unpaid invoice (called by creator)
+    pub fn cancel_invoice(
+        env: Env,
+        invoice_id: u64,
+        creator: Address,
+    ) -> Result<InvoiceResult, ContractError> {
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify creator authorization
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        // A sold receivable cannot be cancelled out from under its holder
+        if invoice.creator != creator || invoice.receivable_holder != creator {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if !Self::is_unpaid(&invoice) {
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        invoice.status = InvoiceStatus::Cancelled;
+        invoice.approved_at = None;
+        Self::save_invoice_transition(&env, &invoice, Some(creator), None);
+
+        Ok(InvoiceResult {
+            invoice_id,
+            status: InvoiceStatus::Cancelled,
+            tx_hash: None,
+        })
+    }
+
+    /// Whether an invoice is awaiting payment in a state that expires at its due date
+    fn is_expirable(invoice: &Invoice) -> bool {
+        matches!(
+            invoice.status,
+            InvoiceStatus::Sent | InvoiceStatus::Approved | InvoiceStatus::PartiallyPaid
+        )
+    }
+
+    /// Status an invoice takes when it lapses at its due date. Split
+    /// invoices with paid shares keep those payments with the holder and
+    /// are marked partially settled rather than expired.
+    fn lapsed_status(invoice: &Invoice) -> InvoiceStatus {
+        if invoice.status == InvoiceStatus::PartiallyPaid {
+            InvoiceStatus::PartiallySettled
+        } else {
+            InvoiceStatus::Expired
+        }
+    }
+
+    /// Whether an invoice is still open and awaiting payment
+    fn is_unpaid(invoice: &Invoice) -> bool {
+        matches!(
+            invoice.status,
+            InvoiceStatus::Draft | InvoiceStatus::Sent | InvoiceStatus::Approved
+        )
+    }
 }
 
 #[cfg(test)]
@@ -621,4 +1059,79 @@ mod test_escrow_properties;
 mod test_condition_release_properties;
 
 #[cfg(test)]
-mod test_timeout_refund_properties;
\ No newline at end of file
+mod test_timeout_refund_properties;
+
+#[cfg(test)]
+mod test_recurring_invoice_properties;
+
+#[cfg(test)]
+mod test_invoice_amendment_properties;
+
+#[cfg(test)]
+mod test_invoice_history_properties;
+
+#[cfg(test)]
+mod test_credit_note_properties;
+
+#[cfg(test)]
+mod test_approval_policy_properties;
+
+#[cfg(test)]
+mod test_claimable_invoice_properties;
+
+#[cfg(test)]
+mod test_receivable_properties;
+
+#[cfg(test)]
+mod test_invoice_escrow_properties;
+
+#[cfg(test)]
+mod test_dispute_window_properties;
+
+#[cfg(test)]
+mod test_split_invoice_properties;
+
+#[cfg(test)]
+mod test_invoice_batch_properties;
+
+#[cfg(test)]
+mod test_oracle_pricing_properties;
+
+#[cfg(test)]
+mod test_invoice_numbering_properties;
+
+#[cfg(test)]
+mod test_payment_request_properties;
+
+#[cfg(test)]
+mod test_netting_properties;
+
+#[cfg(test)]
+mod test_batch_payout_properties;
+
+#[cfg(test)]
+mod test_payment_split_properties;
+
+#[cfg(test)]
+mod test_stream_properties;
+
+#[cfg(test)]
+mod test_vesting_grant_properties;
+
+#[cfg(test)]
+mod test_scheduled_payment_properties;
+
+#[cfg(test)]
+mod test_pending_transfer_properties;
+
+#[cfg(test)]
+mod test_payment_link_properties;
+
+#[cfg(test)]
+mod test_expense_group_properties;
+
+#[cfg(test)]
+mod test_refund_request_properties;
+
+#[cfg(test)]
+mod test_handle_registry_properties;
\ No newline at end of file
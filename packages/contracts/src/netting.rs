@@ -0,0 +1,196 @@
+//! Net settlement of mutual obligations between counterparties
+
+use soroban_sdk::{contractimpl, Address, Env, Map, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    HoldbackStatus, InvoiceStatus, NetTransfer, NettingBatch, TransactionLink, TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+/// Maximum number of invoices settled by one netting call
+///
+/// Between distinct pairs, each invoice writes its record, a history entry,
+/// a net transfer and the debtor's authorization nonce, and consuming a
+/// pending credit writes the credit note and the client's credit queue:
+/// six writes and about thirteen footprint entries per invoice. Six
+/// invoices that all consume a credit use 38 of the 50 writes and 82 of
+/// the 100 footprint entries allowed per transaction.
+pub const MAX_NETTING_INVOICES: u32 = 6;
+
+/// Compute the net transfers settling `balances`, where a positive balance
+/// is owed to the party and a negative one is owed by it.
+///
+/// Debtors are matched against creditors greedily in address order, which
+/// needs at most one transfer fewer than the number of parties involved.
+pub(crate) fn net_transfers(env: &Env, balances: &Map<Address, i128>) -> Vec<NetTransfer> {
+    let mut debtors: Vec<(Address, i128)> = Vec::new(env);
+    let mut creditors: Vec<(Address, i128)> = Vec::new(env);
+    for (party, balance) in balances.iter() {
+        if balance < 0 {
+            debtors.push_back((party, -balance));
+        } else if balance > 0 {
+            creditors.push_back((party, balance));
+        }
+    }
+
+    let mut transfers = Vec::new(env);
+    let (mut d, mut c) = (0, 0);
+    let (mut owed, mut due) = (0i128, 0i128);
+    while d < debtors.len() && c < creditors.len() {
+        let (debtor, debt) = debtors.get_unchecked(d);
+        let (creditor, credit) = creditors.get_unchecked(c);
+        let remaining_debt = debt - owed;
+        let remaining_credit = credit - due;
+        let amount = remaining_debt.min(remaining_credit);
+
+        transfers.push_back(NetTransfer {
+            from: debtor,
+            to: creditor,
+            amount,
+        });
+
+        owed += amount;
+        due += amount;
+        if owed == debt {
+            d += 1;
+            owed = 0;
+        }
+        if due == credit {
+            c += 1;
+            due = 0;
+        }
+    }
+    transfers
+}
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Settle a set of approved invoices among a group of parties by paying
+    /// only the net amount each party owes (callable by anyone)
+    ///
+    /// Every party that is a net debtor must authorize its net payment.
+    /// All included invoices become `Executed` and reference the batch; if
+    /// any invoice cannot be settled, nothing is.
+    pub fn net_settle_invoices(env: Env, invoice_ids: Vec<u64>) -> Result<NettingBatch, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::settle_netting_batch(&env, invoice_ids);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Get netting batch details
+    pub fn get_netting_batch(env: Env, batch_id: u64) -> Result<NettingBatch, ContractError> {
+        storage::get_netting_batch(&env, batch_id)
+            .ok_or(ContractError::NettingBatchNotFound)
+    }
+
+    /// Validate the invoices of a netting batch and settle their net transfers
+    fn settle_netting_batch(env: &Env, invoice_ids: Vec<u64>) -> Result<NettingBatch, ContractError> {
+        if invoice_ids.len() < 2 || invoice_ids.len() > MAX_NETTING_INVOICES {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let batch_id = storage::get_next_netting_batch_id(env);
+        let now = env.ledger().timestamp();
+
+        // Collect each party's net position from the invoices
+        let mut balances: Map<Address, i128> = Map::new(env);
+        let mut invoices = Vec::new(env);
+        let mut seen: Vec<u64> = Vec::new(env);
+        let mut gross_amount: i128 = 0;
+        for invoice_id in invoice_ids.iter() {
+            if seen.contains(invoice_id) {
+                return Err(ContractError::InvalidAmount);
+            }
+            seen.push_back(invoice_id);
+
+            let mut invoice = storage::get_invoice(env, invoice_id)
+                .ok_or(ContractError::InvoiceNotFound)?;
+
+            // Only plain approved invoices can be netted
+            if invoice.status != InvoiceStatus::Approved
+                || !invoice.shares.is_empty()
+                || invoice.denomination_asset.is_some()
+                || invoice.holdback_status != HoldbackStatus::None
+            {
+                return Err(ContractError::InvalidInvoiceStatus);
+            }
+            if now > invoice.due_date {
+                return Err(ContractError::InvoiceExpired);
+            }
+
+            let client = Self::invoice_client(&invoice)?;
+            let amount_due = invoice.amount - Self::apply_pending_credits(env, &invoice);
+            let holder = invoice.receivable_holder.clone();
+            let owed = balances
+                .get(client.clone())
+                .unwrap_or(0)
+                .checked_sub(amount_due)
+                .filter(|balance| *balance != i128::MIN)
+                .ok_or(ContractError::InvalidAmount)?;
+            balances.set(client, owed);
+            let due = balances
+                .get(holder.clone())
+                .unwrap_or(0)
+                .checked_add(amount_due)
+                .ok_or(ContractError::InvalidAmount)?;
+            balances.set(holder, due);
+            gross_amount = gross_amount
+                .checked_add(invoice.amount)
+                .ok_or(ContractError::InvalidAmount)?;
+
+            invoice.status = InvoiceStatus::Executed;
+            invoice.paid_amount = amount_due;
+            invoice.settled_amount = amount_due;
+            invoice.netting_batch = Some(batch_id);
+            invoices.push_back(invoice);
+        }
+
+        // Net debtors authorize their net payment once
+        for (party, balance) in balances.iter() {
+            if balance < 0 {
+                validation::validate_balance(env, &party, -balance)?;
+            }
+        }
+
+        // Settle only the net transfers
+        // (in real implementation, transfer funds between the parties)
+        let transfers = net_transfers(env, &balances);
+        let mut transaction_ids = Vec::new(env);
+        for transfer in transfers.iter() {
+            let transaction = Self::record_transaction(
+                env,
+                TransactionType::Invoice,
+                transfer.from,
+                transfer.to,
+                transfer.amount,
+                String::from_str(env, "net settlement"),
+                TransactionLink::NettingBatch(batch_id),
+            );
+            transaction_ids.push_back(transaction.id);
+        }
+
+        let note = String::from_str(env, "netted");
+        for invoice in invoices.iter() {
+            Self::save_invoice_transition(env, &invoice, None, Some(note.clone()));
+        }
+
+        let batch = NettingBatch {
+            id: batch_id,
+            invoice_ids,
+            gross_amount,
+            transfers,
+            transaction_ids,
+            created_at: now,
+        };
+        storage::set_netting_batch(env, batch_id, &batch);
+
+        Ok(batch)
+    }
+}
@@ -0,0 +1,57 @@
+//! Per-creator invoice numbering and external reference lookups
+
+use soroban_sdk::{contractimpl, Address, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    Invoice,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Set or clear the prefix stamped on the creator's future invoices
+    /// (called by creator)
+    ///
+    /// Invoices keep the prefix they were created with, so changing it does
+    /// not renumber earlier invoices.
+    pub fn set_invoice_number_prefix(
+        env: Env,
+        creator: Address,
+        prefix: Option<String>,
+    ) -> Result<(), ContractError> {
+        // Verify creator authorization
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        storage::set_invoice_prefix(&env, &creator, &prefix);
+        Ok(())
+    }
+
+    /// Get a creator's invoice by the external reference it was created with
+    pub fn get_invoice_by_reference(
+        env: Env,
+        creator: Address,
+        external_ref: String,
+    ) -> Result<Invoice, ContractError> {
+        storage::get_invoice_reference(&env, &creator, &external_ref)
+            .and_then(|invoice_id| storage::get_invoice(&env, invoice_id))
+            .ok_or(ContractError::InvoiceNotFound)
+    }
+
+    /// Get a creator's invoice by its sequential creator number
+    pub fn get_invoice_by_number(env: Env, creator: Address, number: u64) -> Result<Invoice, ContractError> {
+        storage::get_creator_invoice(&env, &creator, number)
+            .and_then(|invoice_id| storage::get_invoice(&env, invoice_id))
+            .ok_or(ContractError::InvoiceNotFound)
+    }
+
+    /// Index a new invoice under an external reference, failing if the
+    /// creator already used it
+    pub(crate) fn reserve_external_ref(env: &Env, invoice: &Invoice, external_ref: &String) -> Result<(), ContractError> {
+        if storage::get_invoice_reference(env, &invoice.creator, external_ref).is_some() {
+            return Err(ContractError::DuplicateReference);
+        }
+        storage::set_invoice_reference(env, &invoice.creator, external_ref, invoice.id);
+        Ok(())
+    }
+}
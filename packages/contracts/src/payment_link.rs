@@ -0,0 +1,161 @@
+//! Payments claimable by whoever holds a secret ("send money by link")
+
+use soroban_sdk::{contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    PaymentLink, PaymentLinkStatus, TransactionLink, TransactionResult, TransactionStatus,
+    TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Lock a payment for a recipient who does not have a wallet yet
+    /// (called by sender)
+    ///
+    /// `claim_key` is the ed25519 public key of a one-off claim keypair whose
+    /// secret key is sent to the recipient out of band (e.g. as a link).
+    /// Whoever signs their address with it through `claim_payment_link`
+    /// before `expires_at` receives the payment.
+    pub fn create_payment_link(
+        env: Env,
+        sender: Address,
+        claim_key: BytesN<32>,
+        amount: i128,
+        memo: String,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        // Validate inputs
+        validation::validate_address(&env, &sender)?;
+        validation::validate_amount(amount)?;
+
+        // Validate expiration time is in the future
+        if expires_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidSchedule);
+        }
+
+        // Lock funds (in real implementation, transfer funds from sender to contract)
+        validation::validate_balance(&env, &sender, amount)?;
+
+        let link = PaymentLink {
+            id: storage::get_next_payment_link_id(&env),
+            sender,
+            amount,
+            memo,
+            claim_key,
+            expires_at,
+            status: PaymentLinkStatus::Open,
+            created_at: env.ledger().timestamp(),
+            claimed_by: None,
+            transaction_id: None,
+        };
+        storage::set_payment_link(&env, link.id, &link);
+
+        Ok(link.id)
+    }
+
+    /// Claim a payment link to any address with a signature by its claim key
+    ///
+    /// `signature` signs the `(contract, link_id, recipient)` tuple
+    /// serialized as XDR, so a claim seen in the mempool cannot be
+    /// redirected to another recipient. The resulting P2P transaction links
+    /// back to the payment link.
+    pub fn claim_payment_link(
+        env: Env,
+        link_id: u64,
+        recipient: Address,
+        signature: BytesN<64>,
+    ) -> Result<TransactionResult, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::claim_open_link(&env, link_id, recipient, signature);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Take back an expired, unclaimed payment link (called by sender)
+    pub fn reclaim_payment_link(env: Env, link_id: u64, sender: Address) -> Result<PaymentLink, ContractError> {
+        let mut link = Self::open_payment_link(&env, link_id)?;
+
+        if link.sender != sender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if env.ledger().timestamp() <= link.expires_at {
+            return Err(ContractError::PaymentLinkNotExpired);
+        }
+
+        // Verify sender authorization
+        validation::validate_signature(&env, &sender, &[], &[])?;
+
+        // Refund funds (in real implementation, transfer back to sender)
+        link.status = PaymentLinkStatus::Reclaimed;
+        storage::set_payment_link(&env, link_id, &link);
+
+        Ok(link)
+    }
+
+    /// Get payment link details
+    pub fn get_payment_link(env: Env, link_id: u64) -> Result<PaymentLink, ContractError> {
+        storage::get_payment_link(&env, link_id)
+            .ok_or(ContractError::PaymentLinkNotFound)
+    }
+
+    /// Get a payment link that has been neither claimed nor reclaimed
+    fn open_payment_link(env: &Env, link_id: u64) -> Result<PaymentLink, ContractError> {
+        let link = storage::get_payment_link(env, link_id)
+            .ok_or(ContractError::PaymentLinkNotFound)?;
+
+        if link.status != PaymentLinkStatus::Open {
+            return Err(ContractError::PaymentLinkClosed);
+        }
+
+        Ok(link)
+    }
+
+    /// Verify the claim signature and pay an open link out to `recipient`
+    fn claim_open_link(
+        env: &Env,
+        link_id: u64,
+        recipient: Address,
+        signature: BytesN<64>,
+    ) -> Result<TransactionResult, ContractError> {
+        let mut link = Self::open_payment_link(env, link_id)?;
+        validation::validate_address(env, &recipient)?;
+
+        if env.ledger().timestamp() > link.expires_at {
+            return Err(ContractError::PaymentLinkExpired);
+        }
+
+        // Verify the claim key signed this link and recipient
+        let message: Bytes = (env.current_contract_address(), link_id, recipient.clone()).to_xdr(env);
+        validation::validate_claim_signature(env, &link.claim_key, &message, &signature);
+
+        // Release funds (in real implementation, transfer from contract to recipient)
+        let transaction = Self::record_transaction(
+            env,
+            TransactionType::P2P,
+            link.sender.clone(),
+            recipient.clone(),
+            link.amount,
+            link.memo.clone(),
+            TransactionLink::PaymentLink(link_id),
+        );
+
+        link.status = PaymentLinkStatus::Claimed;
+        link.claimed_by = Some(recipient);
+        link.transaction_id = Some(transaction.id);
+        storage::set_payment_link(env, link_id, &link);
+
+        Ok(TransactionResult {
+            transaction_id: transaction.id,
+            status: TransactionStatus::Confirmed,
+            tx_hash: String::from_str(env, "p2p_tx_hash"),
+        })
+    }
+}
@@ -0,0 +1,127 @@
+//! Lightweight P2P payment requests ("request money")
+
+use soroban_sdk::{contractimpl, Address, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    PaymentRequest, PaymentRequestStatus, TransactionLink, TransactionResult,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Ask a payer to send money (called by payee)
+    pub fn create_payment_request(
+        env: Env,
+        payee: Address,
+        payer: Address,
+        amount: i128,
+        memo: String,
+    ) -> Result<u64, ContractError> {
+        Self::open_payment_request(&env, payee, payer, None, amount, memo)
+    }
+
+    /// Pay a pending payment request as a P2P transaction (called by payer)
+    ///
+    /// The resulting transaction links back to the request.
+    pub fn pay_payment_request(
+        env: Env,
+        request_id: u64,
+        payer: Address,
+    ) -> Result<TransactionResult, ContractError> {
+        let mut request = Self::pending_payment_request(&env, request_id, &payer)?;
+
+        let result = Self::transfer_p2p(
+            &env,
+            payer,
+            request.payee.clone(),
+            request.amount,
+            request.memo.clone(),
+            TransactionLink::PaymentRequest(request_id),
+        )?;
+
+        request.status = PaymentRequestStatus::Paid;
+        request.transaction_id = Some(result.transaction_id);
+        storage::set_payment_request(&env, request_id, &request);
+
+        Ok(result)
+    }
+
+    /// Decline a pending payment request (called by payer)
+    pub fn decline_payment_request(
+        env: Env,
+        request_id: u64,
+        payer: Address,
+    ) -> Result<PaymentRequest, ContractError> {
+        let mut request = Self::pending_payment_request(&env, request_id, &payer)?;
+
+        // Verify payer authorization
+        validation::validate_signature(&env, &payer, &[], &[])?;
+
+        request.status = PaymentRequestStatus::Declined;
+        storage::set_payment_request(&env, request_id, &request);
+
+        Ok(request)
+    }
+
+    /// Get payment request details
+    pub fn get_payment_request(env: Env, request_id: u64) -> Result<PaymentRequest, ContractError> {
+        storage::get_payment_request(&env, request_id)
+            .ok_or(ContractError::PaymentRequestNotFound)
+    }
+
+    /// Validate and store a new payment request, optionally recording the
+    /// handle the payer was resolved from
+    pub(crate) fn open_payment_request(
+        env: &Env,
+        payee: Address,
+        payer: Address,
+        payer_handle: Option<String>,
+        amount: i128,
+        memo: String,
+    ) -> Result<u64, ContractError> {
+        // Verify payee authorization
+        validation::validate_signature(env, &payee, &[], &[])?;
+        validation::validate_address(env, &payer)?;
+        validation::validate_amount(amount)?;
+
+        if payee == payer {
+            return Err(ContractError::InvalidAddress);
+        }
+
+        let request = PaymentRequest {
+            id: storage::get_next_payment_request_id(env),
+            payee,
+            payer,
+            payer_handle,
+            amount,
+            memo,
+            status: PaymentRequestStatus::Pending,
+            created_at: env.ledger().timestamp(),
+            transaction_id: None,
+        };
+        storage::set_payment_request(env, request.id, &request);
+
+        Ok(request.id)
+    }
+
+    /// Get a pending payment request addressed to `payer`
+    fn pending_payment_request(
+        env: &Env,
+        request_id: u64,
+        payer: &Address,
+    ) -> Result<PaymentRequest, ContractError> {
+        let request = storage::get_payment_request(env, request_id)
+            .ok_or(ContractError::PaymentRequestNotFound)?;
+
+        if request.payer != *payer {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if request.status != PaymentRequestStatus::Pending {
+            return Err(ContractError::PaymentRequestClosed);
+        }
+
+        Ok(request)
+    }
+}
@@ -0,0 +1,175 @@
+//! Percentage-based splits of P2P payments across several recipients
+
+use soroban_sdk::{contractimpl, Address, Env, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    SplitConfig, SplitShare, TransactionLink, TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+/// Basis points in 100%
+const BPS_TOTAL: u32 = 10_000;
+
+/// Maximum number of recipients in one split config
+const MAX_SPLIT_RECIPIENTS: u32 = 10;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Register a reusable payment split (called by owner)
+    ///
+    /// Shares are in basis points and must sum to exactly 10,000, with each
+    /// recipient listed once. The first recipient receives any rounding
+    /// remainder when a payment is split. A share with a `handle` pays
+    /// whatever address the handle resolves to at each payment, for as long
+    /// as the handle keeps its current owner; its `recipient` and
+    /// `handle_owner` are filled in when the split is created.
+    pub fn create_split_config(
+        env: Env,
+        owner: Address,
+        shares: Vec<SplitShare>,
+    ) -> Result<u64, ContractError> {
+        // Verify owner authorization
+        validation::validate_signature(&env, &owner, &[], &[])?;
+
+        if shares.is_empty() || shares.len() > MAX_SPLIT_RECIPIENTS {
+            return Err(ContractError::InvalidSplitConfig);
+        }
+
+        let mut resolved: Vec<SplitShare> = Vec::new(&env);
+        let mut recipients: Vec<Address> = Vec::new(&env);
+        let mut total_bps: u32 = 0;
+        for mut share in shares.iter() {
+            if let Some(handle) = share.handle.clone() {
+                let record = Self::get_handle(env.clone(), handle)?;
+                share.recipient = record.target;
+                share.handle = Some(record.handle);
+                share.handle_owner = Some(record.owner);
+            } else {
+                share.handle_owner = None;
+            }
+            validation::validate_address(&env, &share.recipient)?;
+            if share.bps == 0 || recipients.contains(&share.recipient) {
+                return Err(ContractError::InvalidSplitConfig);
+            }
+            recipients.push_back(share.recipient.clone());
+            total_bps = total_bps.saturating_add(share.bps);
+            resolved.push_back(share);
+        }
+        if total_bps != BPS_TOTAL {
+            return Err(ContractError::InvalidSplitConfig);
+        }
+
+        let config = SplitConfig {
+            id: storage::get_next_split_config_id(&env),
+            owner,
+            shares: resolved,
+            created_at: env.ledger().timestamp(),
+        };
+        storage::set_split_config(&env, config.id, &config);
+
+        Ok(config.id)
+    }
+
+    /// Get split config details
+    pub fn get_split_config(env: Env, split_id: u64) -> Result<SplitConfig, ContractError> {
+        storage::get_split_config(&env, split_id)
+            .ok_or(ContractError::SplitConfigNotFound)
+    }
+
+    /// Execute a P2P payment split across the recipients of a split config
+    ///
+    /// Each recipient receives `amount * bps / 10,000` rounded down, and the
+    /// first recipient also receives the rounding remainder, so the legs sum
+    /// exactly to `amount`. One P2P transaction is recorded per non-zero
+    /// leg; returns their IDs in share order.
+    pub fn execute_p2p_split_transaction(
+        env: Env,
+        sender: Address,
+        split_id: u64,
+        amount: i128,
+        memo: String,
+    ) -> Result<Vec<u64>, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::pay_split(&env, sender, split_id, amount, memo);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Split `amount` by basis points, giving the rounding remainder to the
+    /// first share
+    ///
+    /// Dividing before multiplying keeps every intermediate below `amount`,
+    /// so any valid amount splits without overflow.
+    fn split_amounts(env: &Env, shares: &Vec<SplitShare>, amount: i128) -> Vec<i128> {
+        let total = BPS_TOTAL as i128;
+        let mut amounts = Vec::new(env);
+        let mut allocated: i128 = 0;
+        for share in shares.iter() {
+            let bps = share.bps as i128;
+            let leg = (amount / total) * bps + (amount % total) * bps / total;
+            allocated += leg;
+            amounts.push_back(leg);
+        }
+        if let Some(first) = amounts.first() {
+            amounts.set(0, first + amount - allocated);
+        }
+        amounts
+    }
+
+    /// Validate a split payment and record one transaction per leg
+    fn pay_split(
+        env: &Env,
+        sender: Address,
+        split_id: u64,
+        amount: i128,
+        memo: String,
+    ) -> Result<Vec<u64>, ContractError> {
+        let config = storage::get_split_config(env, split_id)
+            .ok_or(ContractError::SplitConfigNotFound)?;
+
+        // Validate inputs
+        validation::validate_address(env, &sender)?;
+        validation::validate_amount(amount)?;
+
+        // Validate balance and authorization
+        validation::validate_balance(env, &sender, amount)?;
+
+        // Resolve handle shares to where they point now, as long as the
+        // handle still has the owner it had when the split was created
+        let mut recipients: Vec<Address> = Vec::new(env);
+        for share in config.shares.iter() {
+            match (share.handle, share.handle_owner) {
+                (Some(handle), Some(owner)) => recipients.push_back(Self::resolve_handle_of(env, handle, &owner)?),
+                _ => recipients.push_back(share.recipient),
+            }
+        }
+
+        // Execute each leg
+        // (in real implementation, transfer funds from sender to each recipient)
+        let amounts = Self::split_amounts(env, &config.shares, amount);
+        let mut transaction_ids = Vec::new(env);
+        for (recipient, leg) in recipients.iter().zip(amounts.iter()) {
+            if leg == 0 {
+                continue;
+            }
+            let transaction = Self::record_transaction(
+                env,
+                TransactionType::P2P,
+                sender.clone(),
+                recipient,
+                leg,
+                memo.clone(),
+                TransactionLink::SplitConfig(split_id),
+            );
+            transaction_ids.push_back(transaction.id);
+        }
+
+        Ok(transaction_ids)
+    }
+}
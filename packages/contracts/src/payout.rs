@@ -0,0 +1,80 @@
+//! Batch payouts from one sender to many recipients
+
+use soroban_sdk::{contractimpl, Address, Env, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    PayoutEntry, TransactionLink, TransactionType,
+};
+use crate::validation;
+
+/// Maximum number of legs in one batch payout. Each leg writes one
+/// transaction entry, so this keeps a batch within the per-transaction
+/// ledger write limit.
+pub const MAX_BATCH_PAYOUT: u32 = 20;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Pay many recipients from one sender in a single call
+    ///
+    /// The sender authorizes the batch once. Every entry is validated before
+    /// anything is paid, and one transaction is recorded per leg; either all
+    /// legs are paid or none are. Returns the transaction IDs in entry order.
+    pub fn execute_batch_payout(
+        env: Env,
+        sender: Address,
+        entries: Vec<PayoutEntry>,
+    ) -> Result<Vec<u64>, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::pay_out_batch(&env, sender, entries, Vec::new(&env));
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Validate every payout leg, then record one transaction per leg with
+    /// the matching entry of `links` (or no link when `links` is shorter)
+    pub(crate) fn pay_out_batch(
+        env: &Env,
+        sender: Address,
+        entries: Vec<PayoutEntry>,
+        links: Vec<TransactionLink>,
+    ) -> Result<Vec<u64>, ContractError> {
+        if entries.is_empty() || entries.len() > MAX_BATCH_PAYOUT {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Validate inputs
+        validation::validate_address(env, &sender)?;
+        let mut total: i128 = 0;
+        for entry in entries.iter() {
+            validation::validate_address(env, &entry.recipient)?;
+            validation::validate_amount(entry.amount)?;
+            total = total.checked_add(entry.amount).ok_or(ContractError::InvalidAmount)?;
+        }
+
+        // Validate balance and authorization for the whole batch
+        validation::validate_balance(env, &sender, total)?;
+
+        // Execute each leg
+        // (in real implementation, transfer funds from sender to each recipient)
+        let mut transaction_ids = Vec::new(env);
+        for (i, entry) in entries.iter().enumerate() {
+            let transaction = Self::record_transaction(
+                env,
+                TransactionType::Basic,
+                sender.clone(),
+                entry.recipient,
+                entry.amount,
+                entry.memo,
+                links.get(i as u32).unwrap_or(TransactionLink::None),
+            );
+            transaction_ids.push_back(transaction.id);
+        }
+
+        Ok(transaction_ids)
+    }
+}
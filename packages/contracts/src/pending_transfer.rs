@@ -0,0 +1,154 @@
+//! Two-phase P2P transfers held until the recipient claims them
+
+use soroban_sdk::{contractimpl, Address, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    Transaction, TransactionLink, TransactionResult, TransactionStatus, TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Send a P2P transfer the recipient must claim before `expires_at`
+    /// (called by sender)
+    ///
+    /// The transaction stays `Pending` and the sender's funds stay held
+    /// until it is claimed, cancelled by the sender, or expired.
+    pub fn send_pending_p2p_transfer(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        memo: String,
+        expires_at: u64,
+    ) -> Result<TransactionResult, ContractError> {
+        Self::hold_p2p_transfer(&env, sender, recipient, amount, memo, expires_at, TransactionLink::None)
+    }
+
+    /// Claim a pending transfer before it expires (called by recipient)
+    pub fn claim_pending_transfer(
+        env: Env,
+        transaction_id: u64,
+        recipient: Address,
+    ) -> Result<Transaction, ContractError> {
+        let (transaction, expires_at) = Self::pending_transfer(&env, transaction_id)?;
+
+        if transaction.recipient != recipient {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if env.ledger().timestamp() > expires_at {
+            return Err(ContractError::TransferExpired);
+        }
+
+        // Verify recipient authorization
+        validation::validate_signature(&env, &recipient, &[], &[])?;
+
+        // Release funds (in real implementation, transfer from contract to recipient)
+        Ok(Self::close_pending_transfer(&env, transaction, TransactionStatus::Confirmed))
+    }
+
+    /// Cancel an unclaimed transfer (called by sender)
+    pub fn cancel_pending_transfer(
+        env: Env,
+        transaction_id: u64,
+        sender: Address,
+    ) -> Result<Transaction, ContractError> {
+        let (transaction, _) = Self::pending_transfer(&env, transaction_id)?;
+
+        if transaction.sender != sender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Verify sender authorization
+        validation::validate_signature(&env, &sender, &[], &[])?;
+
+        // Refund funds (in real implementation, transfer back to sender)
+        Ok(Self::close_pending_transfer(&env, transaction, TransactionStatus::Cancelled))
+    }
+
+    /// Cancel an expired, unclaimed transfer (callable by anyone)
+    pub fn expire_pending_transfer(env: Env, transaction_id: u64) -> Result<Transaction, ContractError> {
+        let (transaction, expires_at) = Self::pending_transfer(&env, transaction_id)?;
+
+        if env.ledger().timestamp() <= expires_at {
+            return Err(ContractError::TransferNotExpired);
+        }
+
+        // Refund funds (in real implementation, transfer back to sender)
+        Ok(Self::close_pending_transfer(&env, transaction, TransactionStatus::Cancelled))
+    }
+
+    /// Get the claim deadline of a pending transfer
+    pub fn get_pending_transfer_expiry(env: Env, transaction_id: u64) -> Result<u64, ContractError> {
+        Self::pending_transfer(&env, transaction_id).map(|(_, expires_at)| expires_at)
+    }
+
+    /// Validate and record a pending P2P transfer, linking it to the record
+    /// the recipient was resolved from
+    pub(crate) fn hold_p2p_transfer(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        memo: String,
+        expires_at: u64,
+        link: TransactionLink,
+    ) -> Result<TransactionResult, ContractError> {
+        // Validate inputs
+        validation::validate_address(env, &sender)?;
+        validation::validate_address(env, &recipient)?;
+        validation::validate_amount(amount)?;
+
+        // Validate expiration time is in the future
+        if expires_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidSchedule);
+        }
+
+        // Hold funds (in real implementation, transfer funds from sender to contract)
+        validation::validate_balance(env, &sender, amount)?;
+
+        let transaction = Transaction {
+            id: storage::get_next_transaction_id(env),
+            transaction_type: TransactionType::P2P,
+            sender,
+            recipient,
+            amount,
+            status: TransactionStatus::Pending,
+            timestamp: env.ledger().timestamp(),
+            metadata: memo,
+            link,
+        };
+        storage::set_transaction(env, transaction.id, &transaction);
+        storage::set_pending_transfer_expiry(env, transaction.id, expires_at);
+
+        Ok(TransactionResult {
+            transaction_id: transaction.id,
+            status: TransactionStatus::Pending,
+            tx_hash: String::from_str(env, "p2p_tx_hash"),
+        })
+    }
+
+    /// Get a pending transfer and its claim deadline
+    fn pending_transfer(env: &Env, transaction_id: u64) -> Result<(Transaction, u64), ContractError> {
+        let transaction = storage::get_transaction(env, transaction_id)
+            .ok_or(ContractError::TransactionNotFound)?;
+        let expires_at = storage::get_pending_transfer_expiry(env, transaction_id)
+            .ok_or(ContractError::TransferNotPending)?;
+
+        if transaction.status != TransactionStatus::Pending {
+            return Err(ContractError::TransferNotPending);
+        }
+
+        Ok((transaction, expires_at))
+    }
+
+    /// Settle a pending transfer with its final status
+    fn close_pending_transfer(env: &Env, mut transaction: Transaction, status: TransactionStatus) -> Transaction {
+        transaction.status = status;
+        storage::set_transaction(env, transaction.id, &transaction);
+        storage::remove_pending_transfer_expiry(env, transaction.id);
+        transaction
+    }
+}
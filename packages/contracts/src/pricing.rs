@@ -0,0 +1,169 @@
+//! Invoices priced in one asset and settled in another through a price oracle
+
+use soroban_sdk::{contractclient, contractimpl, Address, Env};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    Invoice, InvoiceResult, InvoiceStatus, PriceData,
+};
+use crate::validation;
+use crate::storage;
+
+/// Fixed-point scale of quoted conversion rates (7 decimals, as Stellar assets)
+const RATE_SCALE: i128 = 10_000_000;
+
+/// Basis points in 100%
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Price oracle interface (SEP-40 style) quoting assets in a common base asset
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    /// Most recent price of `asset`, if the oracle has one
+    fn lastprice(env: Env, asset: Address) -> Option<PriceData>;
+}
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Register the price oracle used to convert invoice amounts (called by admin)
+    pub fn set_price_oracle(env: Env, admin: Address, oracle: Address) -> Result<(), ContractError> {
+        // Verify admin authorization
+        validation::validate_signature(&env, &admin, &[], &[])?;
+
+        if storage::get_admin(&env) != Some(admin) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        storage::set_price_oracle(&env, &oracle);
+        Ok(())
+    }
+
+    /// Price a draft invoice in `denomination_asset` and settle it in
+    /// `settlement_asset` (called by creator)
+    ///
+    /// The amount due is converted at execution using the registered oracle.
+    /// Both prices must be at most `max_price_age` seconds old.
+    pub fn set_invoice_pricing(
+        env: Env,
+        invoice_id: u64,
+        creator: Address,
+        denomination_asset: Address,
+        settlement_asset: Address,
+        max_price_age: u64,
+    ) -> Result<Invoice, ContractError> {
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify creator authorization
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        if invoice.creator != creator {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Split invoices settle each share as issued
+        if invoice.status != InvoiceStatus::Draft || !invoice.shares.is_empty() {
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        if max_price_age == 0 {
+            return Err(ContractError::InvalidSchedule);
+        }
+
+        invoice.denomination_asset = Some(denomination_asset);
+        invoice.settlement_asset = Some(settlement_asset);
+        invoice.max_price_age = max_price_age;
+        invoice.version += 1;
+        storage::set_invoice(&env, invoice_id, &invoice);
+
+        Ok(invoice)
+    }
+
+    /// Approve an oracle-priced invoice, accepting the current conversion
+    /// rate with up to `max_slippage_bps` of adverse movement by execution
+    /// (called by client or approver)
+    ///
+    /// Under a multi-approver policy, the rate and slippage bound are taken
+    /// from the final approval that satisfies the policy.
+    pub fn approve_invoice_with_quote(
+        env: Env,
+        invoice_id: u64,
+        approver: Address,
+        version: u32,
+        max_slippage_bps: u32,
+    ) -> Result<InvoiceResult, ContractError> {
+        let invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        if invoice.denomination_asset.is_none() || max_slippage_bps as i128 > BPS_DENOMINATOR {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Self::approve_invoice_version(env, invoice_id, approver, version, Some(max_slippage_bps))
+    }
+
+    /// Convert an invoice amount into its settlement asset. Invoices that are
+    /// not oracle-priced settle one-to-one.
+    pub(crate) fn convert_invoice_amount(env: &Env, invoice: &Invoice, amount: i128) -> Result<i128, ContractError> {
+        if invoice.denomination_asset.is_none() || amount <= 0 {
+            return Ok(amount);
+        }
+
+        // A quote must have been agreed at approval
+        if invoice.quoted_rate <= 0 {
+            return Err(ContractError::PriceUnavailable);
+        }
+
+        // Only rate moves against the client count as slippage
+        let rate = Self::oracle_rate(env, invoice)?;
+        let adverse_move = (rate - invoice.quoted_rate)
+            .checked_mul(BPS_DENOMINATOR)
+            .ok_or(ContractError::SlippageExceeded)?;
+        let tolerated_move = invoice
+            .quoted_rate
+            .checked_mul(invoice.max_slippage_bps as i128)
+            .ok_or(ContractError::SlippageExceeded)?;
+        if adverse_move > tolerated_move {
+            return Err(ContractError::SlippageExceeded);
+        }
+
+        amount
+            .checked_mul(rate)
+            .map(|scaled| scaled / RATE_SCALE)
+            .ok_or(ContractError::InvalidAmount)
+    }
+
+    /// Current rate from the invoice's denomination asset to its settlement
+    /// asset, scaled by `RATE_SCALE`
+    pub(crate) fn oracle_rate(env: &Env, invoice: &Invoice) -> Result<i128, ContractError> {
+        let oracle = storage::get_price_oracle(env).ok_or(ContractError::OracleNotConfigured)?;
+        let oracle = PriceOracleClient::new(env, &oracle);
+
+        let (Some(denomination), Some(settlement)) = (&invoice.denomination_asset, &invoice.settlement_asset) else {
+            return Err(ContractError::PriceUnavailable);
+        };
+        let denomination_price = Self::fresh_price(env, &oracle, denomination, invoice.max_price_age)?;
+        let settlement_price = Self::fresh_price(env, &oracle, settlement, invoice.max_price_age)?;
+
+        denomination_price
+            .checked_mul(RATE_SCALE)
+            .map(|scaled| scaled / settlement_price)
+            .ok_or(ContractError::InvalidAmount)
+    }
+
+    /// Fetch a positive oracle price no older than `max_age` seconds
+    fn fresh_price(
+        env: &Env,
+        oracle: &PriceOracleClient,
+        asset: &Address,
+        max_age: u64,
+    ) -> Result<i128, ContractError> {
+        match oracle.lastprice(asset) {
+            Some(data)
+                if data.price > 0
+                    && env.ledger().timestamp().saturating_sub(data.timestamp) <= max_age =>
+            {
+                Ok(data.price)
+            }
+            _ => Err(ContractError::PriceUnavailable),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Transferable invoice receivables for factoring
+
+use soroban_sdk::{contractimpl, Address, Env, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    InvoiceStatus, ReceivableTransfer,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Transfer the right to receive payment on an invoice (called by current holder)
+    ///
+    /// Only outstanding receivables can be sold, i.e. invoices that are
+    /// `Sent` or `Approved`. `execute_invoice` pays whoever holds the
+    /// receivable at execution time.
+    pub fn transfer_receivable(
+        env: Env,
+        invoice_id: u64,
+        holder: Address,
+        new_holder: Address,
+    ) -> Result<Address, ContractError> {
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify holder authorization
+        validation::validate_signature(&env, &holder, &[], &[])?;
+        validation::validate_address(&env, &new_holder)?;
+
+        if invoice.receivable_holder != holder {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if invoice.status != InvoiceStatus::Sent && invoice.status != InvoiceStatus::Approved {
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        storage::append_receivable_transfer(
+            &env,
+            invoice_id,
+            &ReceivableTransfer {
+                from: holder.clone(),
+                to: new_holder.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        invoice.receivable_holder = new_holder.clone();
+        let note = String::from_str(&env, "receivable transferred");
+        Self::save_invoice_transition(&env, &invoice, Some(holder), Some(note));
+
+        Ok(new_holder)
+    }
+
+    /// Get the chain of receivable transfers for an invoice, oldest first
+    pub fn get_receivable_transfers(env: Env, invoice_id: u64) -> Result<Vec<ReceivableTransfer>, ContractError> {
+        if storage::get_invoice(&env, invoice_id).is_none() {
+            return Err(ContractError::InvoiceNotFound);
+        }
+        Ok(storage::get_receivable_transfers(&env, invoice_id))
+    }
+}
@@ -0,0 +1,238 @@
+//! Recurring invoice (subscription billing) functions
+
+use soroban_sdk::{contractimpl, Address, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    Invoice, InvoiceResult, InvoiceStatus, RecurringInvoice, RecurringInvoiceResult,
+    RecurringInvoiceStatus, RecurringSchedule,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Create a recurring invoice template
+    ///
+    /// One invoice is materialized per `period` starting at `start`, until
+    /// either `end_date` is reached or `max_cycles` invoices have been issued.
+    pub fn create_recurring_invoice(
+        env: Env,
+        creator: Address,
+        client: Address,
+        amount: i128,
+        description: String,
+        schedule: RecurringSchedule,
+    ) -> Result<RecurringInvoiceResult, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        // Validate inputs
+        validation::validate_address(&env, &creator)?;
+        validation::validate_address(&env, &client)?;
+        validation::validate_amount(amount)?;
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        // A subscription needs a positive period and must be bounded
+        let bounded = match (schedule.end_date, schedule.max_cycles) {
+            (None, None) => false,
+            (Some(end), _) if end <= schedule.start || end <= env.ledger().timestamp() => false,
+            (_, Some(0)) => false,
+            _ => true,
+        };
+        if schedule.period == 0 || !bounded {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidSchedule);
+        }
+
+        // Create recurring invoice template
+        let recurring_id = storage::get_next_recurring_id(&env);
+        let recurring = RecurringInvoice {
+            id: recurring_id,
+            creator,
+            client,
+            amount,
+            description,
+            schedule,
+            cycles_issued: 0,
+            pull_authorized: false,
+            status: RecurringInvoiceStatus::Active,
+            created_at: env.ledger().timestamp(),
+        };
+        storage::set_recurring_invoice(&env, recurring_id, &recurring);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(RecurringInvoiceResult {
+            recurring_id,
+            status: RecurringInvoiceStatus::Active,
+        })
+    }
+
+    /// Authorize pull-payment of every cycle of a subscription (called by client)
+    pub fn authorize_recurring_payment(
+        env: Env,
+        recurring_id: u64,
+        client: Address,
+    ) -> Result<RecurringInvoiceResult, ContractError> {
+        let mut recurring = storage::get_recurring_invoice(&env, recurring_id)
+            .ok_or(ContractError::RecurringInvoiceNotFound)?;
+
+        // Verify client authorization
+        validation::validate_signature(&env, &client, &[], &[])?;
+
+        if recurring.client != client {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if recurring.status != RecurringInvoiceStatus::Active {
+            return Err(ContractError::RecurringInvoiceInactive);
+        }
+
+        recurring.pull_authorized = true;
+        storage::set_recurring_invoice(&env, recurring_id, &recurring);
+
+        Ok(RecurringInvoiceResult {
+            recurring_id,
+            status: recurring.status,
+        })
+    }
+
+    /// Cancel a subscription (called by client or creator)
+    ///
+    /// No further invoices are issued and any pull-payment authorization is
+    /// revoked, so already issued invoices need the client's signature again.
+    pub fn cancel_recurring_invoice(
+        env: Env,
+        recurring_id: u64,
+        caller: Address,
+    ) -> Result<RecurringInvoiceResult, ContractError> {
+        let mut recurring = storage::get_recurring_invoice(&env, recurring_id)
+            .ok_or(ContractError::RecurringInvoiceNotFound)?;
+
+        // Verify caller authorization
+        validation::validate_signature(&env, &caller, &[], &[])?;
+
+        if recurring.client != caller && recurring.creator != caller {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if recurring.status != RecurringInvoiceStatus::Active {
+            return Err(ContractError::RecurringInvoiceInactive);
+        }
+
+        recurring.status = RecurringInvoiceStatus::Cancelled;
+        recurring.pull_authorized = false;
+        storage::set_recurring_invoice(&env, recurring_id, &recurring);
+
+        Ok(RecurringInvoiceResult {
+            recurring_id,
+            status: RecurringInvoiceStatus::Cancelled,
+        })
+    }
+
+    /// Materialize the next billing cycle as an invoice (callable by anyone)
+    ///
+    /// The invoice is due one period after its cycle starts, or one period
+    /// after it is generated if the cycle is generated late. If the client
+    /// has authorized pull-payment and has no approval policy it is created
+    /// `Approved` and can be executed directly, otherwise it is created
+    /// `Sent` and awaits approval.
+    pub fn generate_recurring_invoice(
+        env: Env,
+        recurring_id: u64,
+    ) -> Result<InvoiceResult, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let mut recurring = storage::get_recurring_invoice(&env, recurring_id)
+            .ok_or(ContractError::RecurringInvoiceNotFound)?;
+
+        if recurring.status != RecurringInvoiceStatus::Active {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::RecurringInvoiceInactive);
+        }
+
+        // Check the next cycle has started
+        let period_start = Self::cycle_start(&recurring, recurring.cycles_issued);
+        if env.ledger().timestamp() < period_start {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::CycleNotDue);
+        }
+
+        // Pull authorization cannot stand in for a multi-approver policy
+        let status = if recurring.pull_authorized
+            && storage::get_approval_policy(&env, &recurring.client).is_none()
+        {
+            InvoiceStatus::Approved
+        } else {
+            InvoiceStatus::Sent
+        };
+        let issued_from = period_start.max(env.ledger().timestamp());
+        let mut invoice = Self::build_invoice(
+            &env,
+            recurring.creator.clone(),
+            Some(recurring.client.clone()),
+            recurring.amount,
+            recurring.description.clone(),
+            issued_from.saturating_add(recurring.schedule.period),
+            status.clone(),
+        );
+        invoice.recurring_id = Some(recurring_id);
+        Self::save_invoice_transition(&env, &invoice, Some(recurring.creator.clone()), None);
+
+        // Complete the template once the last cycle has been issued
+        recurring.cycles_issued += 1;
+        if !Self::has_next_cycle(&recurring) {
+            recurring.status = RecurringInvoiceStatus::Completed;
+        }
+        storage::set_recurring_invoice(&env, recurring_id, &recurring);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(InvoiceResult {
+            invoice_id: invoice.id,
+            status,
+            tx_hash: None,
+        })
+    }
+
+    /// Get recurring invoice template details
+    pub fn get_recurring_invoice(env: Env, recurring_id: u64) -> Result<RecurringInvoice, ContractError> {
+        storage::get_recurring_invoice(&env, recurring_id)
+            .ok_or(ContractError::RecurringInvoiceNotFound)
+    }
+
+    /// Start time of the given (zero-based) billing cycle
+    fn cycle_start(recurring: &RecurringInvoice, cycle: u32) -> u64 {
+        let schedule = &recurring.schedule;
+        schedule
+            .start
+            .saturating_add(schedule.period.saturating_mul(cycle as u64))
+    }
+
+    /// Whether the template can issue another cycle
+    fn has_next_cycle(recurring: &RecurringInvoice) -> bool {
+        if let Some(max_cycles) = recurring.schedule.max_cycles {
+            if recurring.cycles_issued >= max_cycles {
+                return false;
+            }
+        }
+        if let Some(end_date) = recurring.schedule.end_date {
+            if Self::cycle_start(recurring, recurring.cycles_issued) >= end_date {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether an invoice can be paid without a fresh client signature
+    pub(crate) fn is_pull_authorized(env: &Env, invoice: &Invoice) -> bool {
+        invoice
+            .recurring_id
+            .and_then(|id| storage::get_recurring_invoice(env, id))
+            .map(|recurring| recurring.pull_authorized && invoice.client == Some(recurring.client))
+            .unwrap_or(false)
+    }
+}
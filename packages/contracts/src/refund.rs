@@ -0,0 +1,171 @@
+//! Refund requests for confirmed P2P transactions
+
+use soroban_sdk::{contractimpl, Address, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    RefundRequest, RefundRequestStatus, TransactionLink, TransactionResult, TransactionStatus,
+    TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Ask the recipient of a confirmed P2P transaction for money back
+    /// (called by the original sender)
+    pub fn request_refund(
+        env: Env,
+        transaction_id: u64,
+        sender: Address,
+        reason: String,
+    ) -> Result<u64, ContractError> {
+        let transaction = storage::get_transaction(&env, transaction_id)
+            .ok_or(ContractError::TransactionNotFound)?;
+
+        if transaction.sender != sender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Verify sender authorization
+        validation::validate_signature(&env, &sender, &[], &[])?;
+
+        // Refunds themselves cannot be refunded
+        if transaction.transaction_type != TransactionType::P2P
+            || transaction.status != TransactionStatus::Confirmed
+            || matches!(transaction.link, TransactionLink::RefundRequest(_))
+        {
+            return Err(ContractError::TransactionNotRefundable);
+        }
+
+        if storage::get_refunded_total(&env, transaction_id) >= transaction.amount {
+            return Err(ContractError::RefundExceedsOriginal);
+        }
+
+        let request = RefundRequest {
+            id: storage::get_next_refund_request_id(&env),
+            transaction_id,
+            sender,
+            recipient: transaction.recipient,
+            reason,
+            status: RefundRequestStatus::Pending,
+            refunded_amount: 0,
+            refund_tx_id: None,
+            created_at: env.ledger().timestamp(),
+        };
+        storage::set_refund_request(&env, request.id, &request);
+
+        Ok(request.id)
+    }
+
+    /// Approve a full or partial refund (called by the original recipient)
+    ///
+    /// Records a reverse P2P transaction linked to the request. The total
+    /// refunded against a transaction can never exceed its amount.
+    pub fn approve_refund(
+        env: Env,
+        request_id: u64,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<TransactionResult, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::refund_transaction(&env, request_id, recipient, amount);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Decline a pending refund request (called by the original recipient)
+    pub fn decline_refund(env: Env, request_id: u64, recipient: Address) -> Result<RefundRequest, ContractError> {
+        let mut request = Self::pending_refund_request(&env, request_id, &recipient)?;
+
+        // Verify recipient authorization
+        validation::validate_signature(&env, &recipient, &[], &[])?;
+
+        request.status = RefundRequestStatus::Declined;
+        storage::set_refund_request(&env, request_id, &request);
+
+        Ok(request)
+    }
+
+    /// Get refund request details
+    pub fn get_refund_request(env: Env, request_id: u64) -> Result<RefundRequest, ContractError> {
+        storage::get_refund_request(&env, request_id)
+            .ok_or(ContractError::RefundRequestNotFound)
+    }
+
+    /// Get the total refunded against a transaction
+    pub fn get_refunded_total(env: Env, transaction_id: u64) -> Result<i128, ContractError> {
+        storage::get_transaction(&env, transaction_id)
+            .ok_or(ContractError::TransactionNotFound)?;
+        Ok(storage::get_refunded_total(&env, transaction_id))
+    }
+
+    /// Get a pending refund request addressed to `recipient`
+    fn pending_refund_request(
+        env: &Env,
+        request_id: u64,
+        recipient: &Address,
+    ) -> Result<RefundRequest, ContractError> {
+        let request = storage::get_refund_request(env, request_id)
+            .ok_or(ContractError::RefundRequestNotFound)?;
+
+        if request.recipient != *recipient {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if request.status != RefundRequestStatus::Pending {
+            return Err(ContractError::RefundRequestClosed);
+        }
+
+        Ok(request)
+    }
+
+    /// Validate a refund against the remaining refundable amount and record it
+    fn refund_transaction(
+        env: &Env,
+        request_id: u64,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<TransactionResult, ContractError> {
+        let mut request = Self::pending_refund_request(env, request_id, &recipient)?;
+        validation::validate_amount(amount)?;
+
+        let original = storage::get_transaction(env, request.transaction_id)
+            .ok_or(ContractError::TransactionNotFound)?;
+        let refunded_total = storage::get_refunded_total(env, request.transaction_id);
+        if amount > original.amount - refunded_total {
+            return Err(ContractError::RefundExceedsOriginal);
+        }
+
+        // Validate balance and authorization
+        validation::validate_balance(env, &recipient, amount)?;
+
+        // Execute the reverse transfer
+        // (in real implementation, transfer funds from recipient back to sender)
+        let transaction = Self::record_transaction(
+            env,
+            TransactionType::P2P,
+            recipient,
+            request.sender.clone(),
+            amount,
+            request.reason.clone(),
+            TransactionLink::RefundRequest(request_id),
+        );
+        storage::set_refunded_total(env, request.transaction_id, refunded_total + amount);
+
+        request.status = RefundRequestStatus::Approved;
+        request.refunded_amount = amount;
+        request.refund_tx_id = Some(transaction.id);
+        storage::set_refund_request(env, request_id, &request);
+
+        Ok(TransactionResult {
+            transaction_id: transaction.id,
+            status: TransactionStatus::Confirmed,
+            tx_hash: String::from_str(env, "p2p_tx_hash"),
+        })
+    }
+}
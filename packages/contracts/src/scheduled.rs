@@ -0,0 +1,159 @@
+//! Future-dated payments executable by anyone once due
+
+use soroban_sdk::{contractimpl, Address, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    HandleRecord, ScheduledPayment, ScheduledPaymentStatus, TransactionLink, TransactionResult,
+    TransactionStatus, TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Lock a payment that executes at `execute_at` (called by sender)
+    pub fn schedule_payment(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        memo: String,
+        execute_at: u64,
+    ) -> Result<u64, ContractError> {
+        Self::lock_scheduled_payment(&env, sender, recipient, None, amount, memo, execute_at)
+    }
+
+    /// Execute a due scheduled payment as a P2P transaction (callable by anyone)
+    ///
+    /// The resulting transaction links back to the scheduled payment.
+    pub fn execute_scheduled_payment(env: Env, payment_id: u64) -> Result<TransactionResult, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::execute_due_payment(&env, payment_id);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Cancel a scheduled payment before it executes (called by sender)
+    pub fn cancel_scheduled_payment(
+        env: Env,
+        payment_id: u64,
+        sender: Address,
+    ) -> Result<ScheduledPayment, ContractError> {
+        let mut payment = Self::pending_scheduled_payment(&env, payment_id)?;
+
+        if payment.sender != sender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Verify sender authorization
+        validation::validate_signature(&env, &sender, &[], &[])?;
+
+        // Release the locked funds (in real implementation, transfer back to sender)
+        payment.status = ScheduledPaymentStatus::Cancelled;
+        storage::set_scheduled_payment(&env, payment_id, &payment);
+
+        Ok(payment)
+    }
+
+    /// Get scheduled payment details
+    pub fn get_scheduled_payment(env: Env, payment_id: u64) -> Result<ScheduledPayment, ContractError> {
+        storage::get_scheduled_payment(&env, payment_id)
+            .ok_or(ContractError::ScheduledPaymentNotFound)
+    }
+
+    /// Validate, lock and store a scheduled payment, optionally paying
+    /// whatever address `recipient_handle` resolves to at execution
+    pub(crate) fn lock_scheduled_payment(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        recipient_handle: Option<HandleRecord>,
+        amount: i128,
+        memo: String,
+        execute_at: u64,
+    ) -> Result<u64, ContractError> {
+        // Validate inputs
+        validation::validate_address(env, &sender)?;
+        validation::validate_address(env, &recipient)?;
+        validation::validate_amount(amount)?;
+
+        // Validate execution time is in the future
+        if execute_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidSchedule);
+        }
+
+        // Lock funds (in real implementation, transfer funds from sender to contract)
+        validation::validate_balance(env, &sender, amount)?;
+
+        let payment = ScheduledPayment {
+            id: storage::get_next_scheduled_payment_id(env),
+            sender,
+            recipient,
+            recipient_handle_owner: recipient_handle.as_ref().map(|record| record.owner.clone()),
+            recipient_handle: recipient_handle.map(|record| record.handle),
+            amount,
+            memo,
+            execute_at,
+            status: ScheduledPaymentStatus::Pending,
+            created_at: env.ledger().timestamp(),
+            transaction_id: None,
+        };
+        storage::set_scheduled_payment(env, payment.id, &payment);
+
+        Ok(payment.id)
+    }
+
+    /// Get a scheduled payment that has neither executed nor been cancelled
+    fn pending_scheduled_payment(env: &Env, payment_id: u64) -> Result<ScheduledPayment, ContractError> {
+        let payment = storage::get_scheduled_payment(env, payment_id)
+            .ok_or(ContractError::ScheduledPaymentNotFound)?;
+
+        if payment.status != ScheduledPaymentStatus::Pending {
+            return Err(ContractError::ScheduledPaymentClosed);
+        }
+
+        Ok(payment)
+    }
+
+    /// Record the transfer of a due scheduled payment
+    fn execute_due_payment(env: &Env, payment_id: u64) -> Result<TransactionResult, ContractError> {
+        let mut payment = Self::pending_scheduled_payment(env, payment_id)?;
+
+        if env.ledger().timestamp() < payment.execute_at {
+            return Err(ContractError::PaymentNotDue);
+        }
+
+        // Payments to a handle go wherever it points now, unless it has
+        // changed hands since the payment was scheduled
+        let handle = payment.recipient_handle.clone();
+        if let (Some(handle), Some(owner)) = (handle, payment.recipient_handle_owner.clone()) {
+            payment.recipient = Self::resolve_handle_of(env, handle, &owner)?;
+        }
+
+        // Execute the transfer (in real implementation, transfer funds from contract to recipient)
+        let transaction = Self::record_transaction(
+            env,
+            TransactionType::P2P,
+            payment.sender.clone(),
+            payment.recipient.clone(),
+            payment.amount,
+            payment.memo.clone(),
+            TransactionLink::ScheduledPayment(payment_id),
+        );
+
+        payment.status = ScheduledPaymentStatus::Executed;
+        payment.transaction_id = Some(transaction.id);
+        storage::set_scheduled_payment(env, payment_id, &payment);
+
+        Ok(TransactionResult {
+            transaction_id: transaction.id,
+            status: TransactionStatus::Confirmed,
+            tx_hash: String::from_str(env, "p2p_tx_hash"),
+        })
+    }
+}
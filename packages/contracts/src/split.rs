@@ -0,0 +1,226 @@
+//! Multi-payer invoices split into fixed per-payer shares
+
+use soroban_sdk::{contractimpl, Address, Env, Map, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    Invoice, InvoiceResult, InvoiceStatus, PayerShare, TransactionLink, TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+/// Maximum number of payers on a single split invoice
+const MAX_INVOICE_PAYERS: u32 = 20;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Create an invoice paid by several parties in fixed shares
+    ///
+    /// `shares` maps each payer to the amount they owe; the invoice amount
+    /// is their sum. Each payer approves and pays their own share, and the
+    /// invoice is executed once every share is paid. Shares still unpaid at
+    /// `due_date` expire: the invoice becomes `PartiallySettled` if some
+    /// shares were already paid to the holder, otherwise `Expired`.
+    pub fn create_split_invoice(
+        env: Env,
+        creator: Address,
+        shares: Map<Address, i128>,
+        description: String,
+        due_date: u64,
+    ) -> Result<InvoiceResult, ContractError> {
+        // Verify creator authorization before allocating a number
+        validation::validate_signature(&env, &creator, &[], &[])?;
+
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        // Validate inputs
+        validation::validate_address(&env, &creator)?;
+        if shares.is_empty() || shares.len() > MAX_INVOICE_PAYERS {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut payer_shares = Vec::new(&env);
+        let mut amount: i128 = 0;
+        for (payer, share) in shares.iter() {
+            amount = match validation::validate_amount(share).map(|_| amount.checked_add(share)) {
+                Ok(Some(total)) => total,
+                _ => {
+                    validation::clear_reentrancy(&env);
+                    return Err(ContractError::InvalidAmount);
+                }
+            };
+            payer_shares.push_back(PayerShare {
+                payer,
+                amount: share,
+                approved: false,
+                paid: false,
+                settlement_tx_id: None,
+            });
+        }
+
+        // Validate due date is in the future
+        if due_date <= env.ledger().timestamp() {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Create and store invoice record without a single client
+        let mut invoice = Self::build_invoice(
+            &env,
+            creator.clone(),
+            None,
+            amount,
+            description,
+            due_date,
+            InvoiceStatus::Draft,
+        );
+        invoice.shares = payer_shares;
+        let invoice_id = invoice.id;
+        Self::save_invoice_transition(&env, &invoice, Some(creator), None);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(InvoiceResult {
+            invoice_id,
+            status: InvoiceStatus::Draft,
+            tx_hash: None,
+        })
+    }
+
+    /// Approve one payer's share of a split invoice (called by that payer)
+    pub fn approve_invoice_share(
+        env: Env,
+        invoice_id: u64,
+        payer: Address,
+    ) -> Result<InvoiceResult, ContractError> {
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        // Verify payer authorization
+        validation::validate_signature(&env, &payer, &[], &[])?;
+
+        let index = Self::payer_share_index(&invoice, &payer)?;
+        Self::check_share_payable(&env, &mut invoice)?;
+
+        let mut share = invoice.shares.get_unchecked(index);
+        if share.approved {
+            return Err(ContractError::InvoiceAlreadyApproved);
+        }
+        share.approved = true;
+        invoice.shares.set(index, share);
+
+        let note = String::from_str(&env, "share approved");
+        Self::save_invoice_transition(&env, &invoice, Some(payer), Some(note));
+
+        Ok(InvoiceResult {
+            invoice_id,
+            status: invoice.status,
+            tx_hash: None,
+        })
+    }
+
+    /// Pay one payer's approved share of a split invoice (called by that payer)
+    ///
+    /// The share is paid to the current receivable holder. The invoice
+    /// becomes `PartiallyPaid` until the last share is paid, then `Executed`.
+    pub fn pay_invoice_share(
+        env: Env,
+        invoice_id: u64,
+        payer: Address,
+    ) -> Result<InvoiceResult, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let mut invoice = storage::get_invoice(&env, invoice_id)
+            .ok_or(ContractError::InvoiceNotFound)?;
+
+        let index = match Self::payer_share_index(&invoice, &payer) {
+            Ok(index) => index,
+            Err(err) => {
+                validation::clear_reentrancy(&env);
+                return Err(err);
+            }
+        };
+        if let Err(err) = Self::check_share_payable(&env, &mut invoice) {
+            validation::clear_reentrancy(&env);
+            return Err(err);
+        }
+
+        let mut share = invoice.shares.get_unchecked(index);
+        if share.paid {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::ShareAlreadyPaid);
+        }
+        if !share.approved {
+            validation::clear_reentrancy(&env);
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        // Validate balance and authorization
+        validation::validate_balance(&env, &payer, share.amount)?;
+
+        // Pay the share to the current receivable holder
+        // (in real implementation, transfer funds from payer to holder)
+        let settlement = Self::record_transaction(
+            &env,
+            TransactionType::Invoice,
+            payer.clone(),
+            invoice.receivable_holder.clone(),
+            share.amount,
+            invoice.description.clone(),
+            TransactionLink::None,
+        );
+        share.paid = true;
+        share.settlement_tx_id = Some(settlement.id);
+        invoice.paid_amount += share.amount;
+        invoice.settled_amount += share.amount;
+        invoice.shares.set(index, share);
+
+        invoice.status = if invoice.shares.iter().all(|s| s.paid) {
+            InvoiceStatus::Executed
+        } else {
+            InvoiceStatus::PartiallyPaid
+        };
+        Self::save_invoice_transition(&env, &invoice, Some(payer), None);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        Ok(InvoiceResult {
+            invoice_id,
+            status: invoice.status,
+            tx_hash: None,
+        })
+    }
+
+    /// Find the payer's share on a split invoice
+    fn payer_share_index(invoice: &Invoice, payer: &Address) -> Result<u32, ContractError> {
+        invoice
+            .shares
+            .iter()
+            .position(|share| share.payer == *payer)
+            .map(|index| index as u32)
+            .ok_or(ContractError::PayerShareNotFound)
+    }
+
+    /// Check a split invoice still accepts share approvals and payments,
+    /// marking it lapsed once the due date has passed
+    fn check_share_payable(env: &Env, invoice: &mut Invoice) -> Result<(), ContractError> {
+        if !matches!(
+            invoice.status,
+            InvoiceStatus::Draft | InvoiceStatus::Sent | InvoiceStatus::PartiallyPaid
+        ) {
+            return Err(ContractError::InvalidInvoiceStatus);
+        }
+
+        if env.ledger().timestamp() > invoice.due_date {
+            invoice.status = Self::lapsed_status(invoice);
+            Self::save_invoice_transition(env, invoice, None, None);
+            return Err(ContractError::InvoiceExpired);
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
 //! Storage utilities for smart contract data
 
-use soroban_sdk::{Address, Env, Vec, Symbol, symbol_short};
-use crate::{Transaction, EscrowContract, Invoice};
+use soroban_sdk::{Address, Env, String, Vec, Symbol, symbol_short};
+use crate::{
+    Transaction, EscrowContract, Invoice, InvoiceStatusEntry, RecurringInvoice, CreditNote,
+    ApprovalPolicy, InvoiceApproval, ReceivableTransfer, PaymentRequest, NettingBatch,
+    SplitConfig, PaymentStream, VestingGrant, ScheduledPayment,
+    PaymentLink, ExpenseGroup, RefundRequest, HandleRecord,
+};
 
 /// Set contract administrator
 pub fn set_admin(env: &Env, admin: &Address) {
@@ -15,6 +20,18 @@ pub fn get_admin(env: &Env) -> Option<Address> {
     env.storage().instance().get(&key)
 }
 
+/// Set the price oracle contract used for invoice conversions
+pub fn set_price_oracle(env: &Env, oracle: &Address) {
+    let key = symbol_short!("oracle");
+    env.storage().instance().set(&key, oracle);
+}
+
+/// Get the price oracle contract
+pub fn get_price_oracle(env: &Env) -> Option<Address> {
+    let key = symbol_short!("oracle");
+    env.storage().instance().get(&key)
+}
+
 /// Get next transaction ID
 pub fn get_next_transaction_id(env: &Env) -> u64 {
     let key = symbol_short!("tx_count");
@@ -73,6 +90,12 @@ pub fn get_next_invoice_id(env: &Env) -> u64 {
     next
 }
 
+/// Get the number of invoices created so far (the highest invoice ID)
+pub fn get_invoice_count(env: &Env) -> u64 {
+    let key = symbol_short!("inv_count");
+    env.storage().instance().get(&key).unwrap_or(0)
+}
+
 /// Store invoice
 pub fn set_invoice(env: &Env, id: u64, invoice: &Invoice) {
     let key = Symbol::new(env, "invoice");
@@ -83,4 +106,432 @@ pub fn set_invoice(env: &Env, id: u64, invoice: &Invoice) {
 pub fn get_invoice(env: &Env, id: u64) -> Option<Invoice> {
     let key = Symbol::new(env, "invoice");
     env.storage().persistent().get(&(key, id))
-}
\ No newline at end of file
+}
+
+/// Get the next sequential invoice number for a creator
+pub fn get_next_creator_invoice_number(env: &Env, creator: &Address) -> u64 {
+    let key = (Symbol::new(env, "inv_num_seq"), creator.clone());
+    let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().persistent().set(&key, &next);
+    next
+}
+
+/// Index an invoice by its creator number
+pub fn set_creator_invoice(env: &Env, creator: &Address, number: u64, invoice_id: u64) {
+    let key = (Symbol::new(env, "inv_num"), creator.clone(), number);
+    env.storage().persistent().set(&key, &invoice_id);
+}
+
+/// Get the invoice ID for a creator number
+pub fn get_creator_invoice(env: &Env, creator: &Address, number: u64) -> Option<u64> {
+    let key = (Symbol::new(env, "inv_num"), creator.clone(), number);
+    env.storage().persistent().get(&key)
+}
+
+/// Index an invoice by its creator's external reference
+pub fn set_invoice_reference(env: &Env, creator: &Address, reference: &String, invoice_id: u64) {
+    let key = (Symbol::new(env, "inv_ref"), creator.clone(), reference.clone());
+    env.storage().persistent().set(&key, &invoice_id);
+}
+
+/// Get the invoice ID for a creator's external reference
+pub fn get_invoice_reference(env: &Env, creator: &Address, reference: &String) -> Option<u64> {
+    let key = (Symbol::new(env, "inv_ref"), creator.clone(), reference.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Set or clear a creator's invoice numbering prefix
+pub fn set_invoice_prefix(env: &Env, creator: &Address, prefix: &Option<String>) {
+    let key = (Symbol::new(env, "inv_prefix"), creator.clone());
+    match prefix {
+        Some(prefix) => env.storage().persistent().set(&key, prefix),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get a creator's invoice numbering prefix
+pub fn get_invoice_prefix(env: &Env, creator: &Address) -> Option<String> {
+    let key = (Symbol::new(env, "inv_prefix"), creator.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Append an entry to an invoice's status history
+pub fn append_invoice_history(env: &Env, id: u64, entry: &InvoiceStatusEntry) {
+    let key = (Symbol::new(env, "inv_hist"), id);
+    let mut history: Vec<InvoiceStatusEntry> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    history.push_back(entry.clone());
+    env.storage().persistent().set(&key, &history);
+}
+
+/// Get an invoice's status history
+pub fn get_invoice_history(env: &Env, id: u64) -> Vec<InvoiceStatusEntry> {
+    let key = Symbol::new(env, "inv_hist");
+    env.storage().persistent().get(&(key, id)).unwrap_or(Vec::new(env))
+}
+
+/// Append a transfer to an invoice's receivable transfer chain
+pub fn append_receivable_transfer(env: &Env, invoice_id: u64, transfer: &ReceivableTransfer) {
+    let key = (Symbol::new(env, "inv_rcv"), invoice_id);
+    let mut transfers: Vec<ReceivableTransfer> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    transfers.push_back(transfer.clone());
+    env.storage().persistent().set(&key, &transfers);
+}
+
+/// Get an invoice's receivable transfer chain
+pub fn get_receivable_transfers(env: &Env, invoice_id: u64) -> Vec<ReceivableTransfer> {
+    let key = Symbol::new(env, "inv_rcv");
+    env.storage().persistent().get(&(key, invoice_id)).unwrap_or(Vec::new(env))
+}
+
+/// Get next recurring invoice ID
+pub fn get_next_recurring_id(env: &Env) -> u64 {
+    let key = symbol_short!("rec_count");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store recurring invoice template
+pub fn set_recurring_invoice(env: &Env, id: u64, recurring: &RecurringInvoice) {
+    let key = Symbol::new(env, "recurring");
+    env.storage().persistent().set(&(key, id), recurring);
+}
+
+/// Get recurring invoice template
+pub fn get_recurring_invoice(env: &Env, id: u64) -> Option<RecurringInvoice> {
+    let key = Symbol::new(env, "recurring");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Get next credit note ID
+pub fn get_next_credit_note_id(env: &Env) -> u64 {
+    let key = symbol_short!("cn_count");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store credit note
+pub fn set_credit_note(env: &Env, id: u64, credit_note: &CreditNote) {
+    let key = Symbol::new(env, "credit_note");
+    env.storage().persistent().set(&(key, id), credit_note);
+}
+
+/// Get credit note
+pub fn get_credit_note(env: &Env, id: u64) -> Option<CreditNote> {
+    let key = Symbol::new(env, "credit_note");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Store IDs of credit notes issued against an invoice
+pub fn set_invoice_credit_notes(env: &Env, invoice_id: u64, ids: &Vec<u64>) {
+    let key = Symbol::new(env, "inv_credits");
+    env.storage().persistent().set(&(key, invoice_id), ids);
+}
+
+/// Get IDs of credit notes issued against an invoice
+pub fn get_invoice_credit_notes(env: &Env, invoice_id: u64) -> Vec<u64> {
+    let key = Symbol::new(env, "inv_credits");
+    env.storage().persistent().get(&(key, invoice_id)).unwrap_or(Vec::new(env))
+}
+
+/// Store IDs of credit notes in `asset` pending application between a
+/// creator and client
+pub fn set_pending_credits(
+    env: &Env,
+    creator: &Address,
+    client: &Address,
+    asset: &Option<Address>,
+    ids: &Vec<u64>,
+) {
+    let key = Symbol::new(env, "credit_queue");
+    env.storage().persistent().set(&(key, creator.clone(), client.clone(), asset.clone()), ids);
+}
+
+/// Get IDs of credit notes in `asset` pending application between a
+/// creator and client
+pub fn get_pending_credits(env: &Env, creator: &Address, client: &Address, asset: &Option<Address>) -> Vec<u64> {
+    let key = Symbol::new(env, "credit_queue");
+    env.storage()
+        .persistent()
+        .get(&(key, creator.clone(), client.clone(), asset.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store a client's approval policy
+pub fn set_approval_policy(env: &Env, client: &Address, policy: &ApprovalPolicy) {
+    let key = Symbol::new(env, "appr_policy");
+    env.storage().persistent().set(&(key, client.clone()), policy);
+}
+
+/// Get a client's approval policy
+pub fn get_approval_policy(env: &Env, client: &Address) -> Option<ApprovalPolicy> {
+    let key = Symbol::new(env, "appr_policy");
+    env.storage().persistent().get(&(key, client.clone()))
+}
+
+/// Remove a client's approval policy
+pub fn remove_approval_policy(env: &Env, client: &Address) {
+    let key = Symbol::new(env, "appr_policy");
+    env.storage().persistent().remove(&(key, client.clone()));
+}
+
+/// Store the approvals recorded for an invoice
+pub fn set_invoice_approvals(env: &Env, invoice_id: u64, approvals: &Vec<InvoiceApproval>) {
+    let key = Symbol::new(env, "inv_approvals");
+    env.storage().persistent().set(&(key, invoice_id), approvals);
+}
+
+/// Get the approvals recorded for an invoice
+pub fn get_invoice_approvals(env: &Env, invoice_id: u64) -> Vec<InvoiceApproval> {
+    let key = Symbol::new(env, "inv_approvals");
+    env.storage().persistent().get(&(key, invoice_id)).unwrap_or(Vec::new(env))
+}
+
+/// Get next payment request ID
+pub fn get_next_payment_request_id(env: &Env) -> u64 {
+    let key = symbol_short!("pr_count");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store payment request
+pub fn set_payment_request(env: &Env, id: u64, request: &PaymentRequest) {
+    let key = Symbol::new(env, "pay_request");
+    env.storage().persistent().set(&(key, id), request);
+}
+
+/// Get payment request
+pub fn get_payment_request(env: &Env, id: u64) -> Option<PaymentRequest> {
+    let key = Symbol::new(env, "pay_request");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Get next netting batch ID
+pub fn get_next_netting_batch_id(env: &Env) -> u64 {
+    let key = symbol_short!("net_count");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store netting batch
+pub fn set_netting_batch(env: &Env, id: u64, batch: &NettingBatch) {
+    let key = Symbol::new(env, "netting");
+    env.storage().persistent().set(&(key, id), batch);
+}
+
+/// Get netting batch
+pub fn get_netting_batch(env: &Env, id: u64) -> Option<NettingBatch> {
+    let key = Symbol::new(env, "netting");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Get next split config ID
+pub fn get_next_split_config_id(env: &Env) -> u64 {
+    let key = symbol_short!("split_cnt");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store split config
+pub fn set_split_config(env: &Env, id: u64, config: &SplitConfig) {
+    let key = Symbol::new(env, "split_cfg");
+    env.storage().persistent().set(&(key, id), config);
+}
+
+/// Get split config
+pub fn get_split_config(env: &Env, id: u64) -> Option<SplitConfig> {
+    let key = Symbol::new(env, "split_cfg");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Get next payment stream ID
+pub fn get_next_stream_id(env: &Env) -> u64 {
+    let key = symbol_short!("strm_cnt");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store payment stream
+pub fn set_stream(env: &Env, id: u64, stream: &PaymentStream) {
+    let key = Symbol::new(env, "stream");
+    env.storage().persistent().set(&(key, id), stream);
+}
+
+/// Get payment stream
+pub fn get_stream(env: &Env, id: u64) -> Option<PaymentStream> {
+    let key = Symbol::new(env, "stream");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Get next vesting grant ID
+pub fn get_next_vesting_grant_id(env: &Env) -> u64 {
+    let key = symbol_short!("grant_cnt");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store vesting grant
+pub fn set_vesting_grant(env: &Env, id: u64, grant: &VestingGrant) {
+    let key = Symbol::new(env, "vest_grant");
+    env.storage().persistent().set(&(key, id), grant);
+}
+
+/// Get vesting grant
+pub fn get_vesting_grant(env: &Env, id: u64) -> Option<VestingGrant> {
+    let key = Symbol::new(env, "vest_grant");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Get next scheduled payment ID
+pub fn get_next_scheduled_payment_id(env: &Env) -> u64 {
+    let key = symbol_short!("sched_cnt");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store scheduled payment
+pub fn set_scheduled_payment(env: &Env, id: u64, payment: &ScheduledPayment) {
+    let key = Symbol::new(env, "sched_pay");
+    env.storage().persistent().set(&(key, id), payment);
+}
+
+/// Get scheduled payment
+pub fn get_scheduled_payment(env: &Env, id: u64) -> Option<ScheduledPayment> {
+    let key = Symbol::new(env, "sched_pay");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Store the claim deadline of a pending transfer
+pub fn set_pending_transfer_expiry(env: &Env, transaction_id: u64, expires_at: u64) {
+    let key = Symbol::new(env, "p2p_expiry");
+    env.storage().persistent().set(&(key, transaction_id), &expires_at);
+}
+
+/// Get the claim deadline of a pending transfer
+pub fn get_pending_transfer_expiry(env: &Env, transaction_id: u64) -> Option<u64> {
+    let key = Symbol::new(env, "p2p_expiry");
+    env.storage().persistent().get(&(key, transaction_id))
+}
+
+/// Remove the claim deadline once a pending transfer is claimed or cancelled
+pub fn remove_pending_transfer_expiry(env: &Env, transaction_id: u64) {
+    let key = Symbol::new(env, "p2p_expiry");
+    env.storage().persistent().remove(&(key, transaction_id));
+}
+
+/// Get next payment link ID
+pub fn get_next_payment_link_id(env: &Env) -> u64 {
+    let key = symbol_short!("link_cnt");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store payment link
+pub fn set_payment_link(env: &Env, id: u64, link: &PaymentLink) {
+    let key = Symbol::new(env, "pay_link");
+    env.storage().persistent().set(&(key, id), link);
+}
+
+/// Get payment link
+pub fn get_payment_link(env: &Env, id: u64) -> Option<PaymentLink> {
+    let key = Symbol::new(env, "pay_link");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Get next expense group ID
+pub fn get_next_expense_group_id(env: &Env) -> u64 {
+    let key = symbol_short!("group_cnt");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store expense group
+pub fn set_expense_group(env: &Env, id: u64, group: &ExpenseGroup) {
+    let key = Symbol::new(env, "exp_group");
+    env.storage().persistent().set(&(key, id), group);
+}
+
+/// Get expense group
+pub fn get_expense_group(env: &Env, id: u64) -> Option<ExpenseGroup> {
+    let key = Symbol::new(env, "exp_group");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Get next refund request ID
+pub fn get_next_refund_request_id(env: &Env) -> u64 {
+    let key = symbol_short!("rfnd_cnt");
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+/// Store refund request
+pub fn set_refund_request(env: &Env, id: u64, request: &RefundRequest) {
+    let key = Symbol::new(env, "refund_req");
+    env.storage().persistent().set(&(key, id), request);
+}
+
+/// Get refund request
+pub fn get_refund_request(env: &Env, id: u64) -> Option<RefundRequest> {
+    let key = Symbol::new(env, "refund_req");
+    env.storage().persistent().get(&(key, id))
+}
+
+/// Store the total refunded against a transaction
+pub fn set_refunded_total(env: &Env, transaction_id: u64, total: i128) {
+    let key = Symbol::new(env, "tx_refunded");
+    env.storage().persistent().set(&(key, transaction_id), &total);
+}
+
+/// Get the total refunded against a transaction
+pub fn get_refunded_total(env: &Env, transaction_id: u64) -> i128 {
+    let key = Symbol::new(env, "tx_refunded");
+    env.storage().persistent().get(&(key, transaction_id)).unwrap_or(0)
+}
+
+/// Store handle record
+pub fn set_handle(env: &Env, handle: &String, record: &HandleRecord) {
+    let key = Symbol::new(env, "handle");
+    env.storage().persistent().set(&(key, handle.clone()), record);
+}
+
+/// Get handle record
+pub fn get_handle(env: &Env, handle: &String) -> Option<HandleRecord> {
+    let key = Symbol::new(env, "handle");
+    env.storage().persistent().get(&(key, handle.clone()))
+}
+
+/// Remove handle record
+pub fn remove_handle(env: &Env, handle: &String) {
+    let key = Symbol::new(env, "handle");
+    env.storage().persistent().remove(&(key, handle.clone()));
+}
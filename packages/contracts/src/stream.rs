@@ -0,0 +1,201 @@
+//! Payment streams that vest linearly per second
+
+use soroban_sdk::{contractimpl, Address, Env, String};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    PaymentStream, StreamStatus, TransactionLink, TransactionType,
+};
+use crate::validation;
+use crate::storage;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Deposit funds that vest linearly to a recipient between `start` and
+    /// `end` (called by sender)
+    ///
+    /// Nothing is withdrawable before `cliff`; at the cliff the amount
+    /// vested since `start` becomes available at once.
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit: i128,
+        start: u64,
+        cliff: Option<u64>,
+        end: u64,
+    ) -> Result<u64, ContractError> {
+        // Validate inputs
+        validation::validate_address(&env, &sender)?;
+        validation::validate_address(&env, &recipient)?;
+        validation::validate_amount(deposit)?;
+
+        if sender == recipient {
+            return Err(ContractError::InvalidAddress);
+        }
+
+        // Validate the schedule ends in the future and the cliff falls within it
+        let cliff = cliff.unwrap_or(start);
+        if start >= end || cliff < start || cliff > end || end <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidSchedule);
+        }
+
+        // Lock the deposit (in real implementation, transfer funds from sender to contract)
+        validation::validate_balance(&env, &sender, deposit)?;
+
+        let stream = PaymentStream {
+            id: storage::get_next_stream_id(&env),
+            sender,
+            recipient,
+            deposit,
+            withdrawn: 0,
+            refunded: 0,
+            start,
+            cliff,
+            end,
+            status: StreamStatus::Active,
+            created_at: env.ledger().timestamp(),
+        };
+        storage::set_stream(&env, stream.id, &stream);
+
+        Ok(stream.id)
+    }
+
+    /// Withdraw everything vested so far (called by recipient)
+    ///
+    /// Returns the amount withdrawn. The stream completes once the full
+    /// deposit has been withdrawn.
+    pub fn withdraw_from_stream(env: Env, stream_id: u64, recipient: Address) -> Result<i128, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::withdraw_vested(&env, stream_id, recipient);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Cancel an active stream (called by sender)
+    ///
+    /// The recipient is paid whatever has vested but not been withdrawn and
+    /// the unvested remainder is returned to the sender.
+    pub fn cancel_stream(env: Env, stream_id: u64, sender: Address) -> Result<PaymentStream, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::cancel_active_stream(&env, stream_id, sender);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Get payment stream details
+    pub fn get_stream(env: Env, stream_id: u64) -> Result<PaymentStream, ContractError> {
+        storage::get_stream(&env, stream_id)
+            .ok_or(ContractError::StreamNotFound)
+    }
+
+    /// Get the amount the recipient could withdraw right now
+    pub fn get_stream_withdrawable(env: Env, stream_id: u64) -> Result<i128, ContractError> {
+        let stream = storage::get_stream(&env, stream_id)
+            .ok_or(ContractError::StreamNotFound)?;
+
+        if stream.status != StreamStatus::Active {
+            return Ok(0);
+        }
+        Ok(Self::vested_amount(&stream, env.ledger().timestamp()) - stream.withdrawn)
+    }
+
+    /// Amount of the deposit vested at `now`
+    fn vested_amount(stream: &PaymentStream, now: u64) -> i128 {
+        if now < stream.cliff {
+            return 0;
+        }
+        if now >= stream.end {
+            return stream.deposit;
+        }
+
+        // Split the multiplication so `deposit * elapsed` cannot overflow
+        let duration = (stream.end - stream.start) as i128;
+        let elapsed = (now - stream.start) as i128;
+        (stream.deposit / duration) * elapsed + (stream.deposit % duration) * elapsed / duration
+    }
+
+    /// Get an active stream
+    fn active_stream(env: &Env, stream_id: u64) -> Result<PaymentStream, ContractError> {
+        let stream = storage::get_stream(env, stream_id)
+            .ok_or(ContractError::StreamNotFound)?;
+
+        if stream.status != StreamStatus::Active {
+            return Err(ContractError::StreamInactive);
+        }
+
+        Ok(stream)
+    }
+
+    /// Record a payment of `amount` from the stream to its recipient
+    fn pay_stream_recipient(env: &Env, stream: &mut PaymentStream, amount: i128) {
+        // (in real implementation, transfer funds from contract to recipient)
+        Self::record_transaction(
+            env,
+            TransactionType::P2P,
+            stream.sender.clone(),
+            stream.recipient.clone(),
+            amount,
+            String::from_str(env, "stream"),
+            TransactionLink::Stream(stream.id),
+        );
+        stream.withdrawn += amount;
+    }
+
+    /// Pay the recipient the vested, unwithdrawn portion of a stream
+    fn withdraw_vested(env: &Env, stream_id: u64, recipient: Address) -> Result<i128, ContractError> {
+        let mut stream = Self::active_stream(env, stream_id)?;
+        if stream.recipient != recipient {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Verify recipient authorization
+        validation::validate_signature(env, &recipient, &[], &[])?;
+
+        let amount = Self::vested_amount(&stream, env.ledger().timestamp()) - stream.withdrawn;
+        if amount <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+
+        Self::pay_stream_recipient(env, &mut stream, amount);
+        if stream.withdrawn == stream.deposit {
+            stream.status = StreamStatus::Completed;
+        }
+        storage::set_stream(env, stream_id, &stream);
+
+        Ok(amount)
+    }
+
+    /// Settle the vested portion with the recipient and refund the rest
+    fn cancel_active_stream(env: &Env, stream_id: u64, sender: Address) -> Result<PaymentStream, ContractError> {
+        let mut stream = Self::active_stream(env, stream_id)?;
+        if stream.sender != sender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Verify sender authorization
+        validation::validate_signature(env, &sender, &[], &[])?;
+
+        let vested = Self::vested_amount(&stream, env.ledger().timestamp());
+        let owed = vested - stream.withdrawn;
+        if owed > 0 {
+            Self::pay_stream_recipient(env, &mut stream, owed);
+        }
+
+        // Refund the unvested remainder (in real implementation, transfer back to sender)
+        stream.refunded = stream.deposit - vested;
+        stream.status = StreamStatus::Cancelled;
+        storage::set_stream(env, stream_id, &stream);
+
+        Ok(stream)
+    }
+}
@@ -0,0 +1,214 @@
+//! Property-based tests for multi-approver invoice approval
+//! Feature: stellar-smart-contract-dapp, Property 16: Approval Policy Enforcement
+//! **Validates: Requirements 4.3**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ApprovalPolicy, ContractError, InvoiceStatus,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Create and send an invoice, returning its ID
+fn sent_invoice(
+    env: &Env,
+    client: &StellarDAppContractClient,
+    creator: &Address,
+    client_addr: &Address,
+    amount: i128,
+) -> u64 {
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(env, "Enterprise license");
+    let invoice_id = client
+        .create_invoice(creator, client_addr, &amount, &description, &due_date, &None)
+        .invoice_id;
+    client.mark_invoice_sent(&invoice_id, creator);
+    invoice_id
+}
+
+/// Property 16: Approval Policy Enforcement
+///
+/// For any k-of-n approval policy and any invoice at or above the policy
+/// threshold amount, the invoice SHALL remain unapproved until exactly k
+/// distinct listed approvers have approved the current version, and every
+/// individual approval SHALL be recorded.
+///
+/// This test runs 100 iterations with varied approver counts, thresholds
+/// and ordering rules.
+#[test]
+fn property_approval_policy_enforcement() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+
+        let approver_count = 2 + (iteration % 4) as u32;
+        let threshold = 1 + (iteration as u32 % approver_count);
+        let ordered = iteration % 2 == 0;
+        let mut approvers = Vec::new(&env);
+        for _ in 0..approver_count {
+            approvers.push_back(Address::generate(&env));
+        }
+        client.set_approval_policy(
+            &client_addr,
+            &ApprovalPolicy {
+                approvers: approvers.clone(),
+                threshold,
+                ordered,
+                min_amount: 10_000,
+            },
+        );
+
+        let amount: i128 = 10_000 + (iteration as i128 * 1_000);
+        let invoice_id = sent_invoice(&env, &client, &creator, &client_addr, amount);
+
+        // Non-listed signers, including the client address itself, cannot approve
+        assert_eq!(
+            client.try_approve_invoice(&invoice_id, &client_addr, &1),
+            Err(Ok(ContractError::Unauthorized))
+        );
+
+        for i in 0..threshold {
+            let approver = approvers.get(i).unwrap();
+
+            // In ordered policies, a later approver cannot jump the queue
+            if ordered && i + 1 < approver_count {
+                let later = approvers.get(i + 1).unwrap();
+                assert_eq!(
+                    client.try_approve_invoice(&invoice_id, &later, &1),
+                    Err(Ok(ContractError::ApprovalOutOfOrder)),
+                    "Iteration {}: Ordered approvals must follow the list",
+                    iteration
+                );
+            }
+
+            let result = client.approve_invoice(&invoice_id, &approver, &1);
+            let expected = if i + 1 == threshold {
+                InvoiceStatus::Approved
+            } else {
+                InvoiceStatus::Sent
+            };
+            assert_eq!(
+                result.status, expected,
+                "Iteration {}: Invoice should only be approved after {} approvals",
+                iteration, threshold
+            );
+
+            // The same approver cannot approve twice
+            if i + 1 < threshold {
+                assert_eq!(
+                    client.try_approve_invoice(&invoice_id, &approver, &1),
+                    Err(Ok(ContractError::DuplicateApproval))
+                );
+            }
+        }
+
+        let approvals = client.get_invoice_approvals(&invoice_id);
+        assert_eq!(
+            approvals.len(),
+            threshold,
+            "Iteration {}: Every individual approval should be recorded",
+            iteration
+        );
+        for (i, approval) in approvals.iter().enumerate() {
+            assert_eq!(approval.approver, approvers.get(i as u32).unwrap());
+            assert_eq!(approval.version, 1);
+        }
+    }
+}
+
+/// Below the threshold amount any single approver suffices, and amendments
+/// discard approvals of earlier versions.
+#[test]
+fn test_approval_policy_threshold_and_amendment() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let manager = Address::generate(&env);
+    let finance = Address::generate(&env);
+    client.set_approval_policy(
+        &client_addr,
+        &ApprovalPolicy {
+            approvers: Vec::from_array(&env, [manager.clone(), finance.clone()]),
+            threshold: 2,
+            ordered: true,
+            min_amount: 5_000,
+        },
+    );
+
+    // Small invoice: finance alone can approve
+    let small = sent_invoice(&env, &client, &creator, &client_addr, 4_999);
+    assert_eq!(
+        client.approve_invoice(&small, &finance, &1).status,
+        InvoiceStatus::Approved
+    );
+
+    // Large invoice: manager then finance
+    let large = sent_invoice(&env, &client, &creator, &client_addr, 5_000);
+    assert_eq!(client.approve_invoice(&large, &manager, &1).status, InvoiceStatus::Sent);
+
+    // Amending resets the approval count for the new version
+    let description = SorobanString::from_str(&env, "Enterprise license (revised)");
+    let due_date = env.ledger().timestamp() + 20_000;
+    client.amend_invoice(&large, &creator, &6_000i128, &description, &due_date);
+    assert_eq!(
+        client.try_approve_invoice(&large, &finance, &2),
+        Err(Ok(ContractError::ApprovalOutOfOrder))
+    );
+    assert_eq!(client.approve_invoice(&large, &manager, &2).status, InvoiceStatus::Sent);
+    assert_eq!(client.approve_invoice(&large, &finance, &2).status, InvoiceStatus::Approved);
+    assert_eq!(client.get_invoice_approvals(&large).len(), 3);
+
+    // Any listed approver may reject
+    let rejected = sent_invoice(&env, &client, &creator, &client_addr, 9_000);
+    let reason = SorobanString::from_str(&env, "Over budget");
+    assert_eq!(
+        client.reject_invoice(&rejected, &finance, &reason).status,
+        InvoiceStatus::Rejected
+    );
+
+    // Malformed policies are rejected
+    let invalid = ApprovalPolicy {
+        approvers: Vec::from_array(&env, [manager.clone(), manager.clone()]),
+        threshold: 1,
+        ordered: false,
+        min_amount: 0,
+    };
+    assert_eq!(
+        client.try_set_approval_policy(&client_addr, &invalid),
+        Err(Ok(ContractError::InvalidApprovalPolicy))
+    );
+    let too_strict = ApprovalPolicy {
+        approvers: Vec::from_array(&env, [manager]),
+        threshold: 2,
+        ordered: false,
+        min_amount: 0,
+    };
+    assert_eq!(
+        client.try_set_approval_policy(&client_addr, &too_strict),
+        Err(Ok(ContractError::InvalidApprovalPolicy))
+    );
+
+    // Removing the policy restores single-signer approval by the client
+    client.remove_approval_policy(&client_addr);
+    assert_eq!(client.get_approval_policy(&client_addr), None);
+    let plain = sent_invoice(&env, &client, &creator, &client_addr, 50_000);
+    assert_eq!(
+        client.approve_invoice(&plain, &client_addr, &1).status,
+        InvoiceStatus::Approved
+    );
+}
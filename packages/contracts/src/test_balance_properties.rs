@@ -95,6 +95,7 @@ fn property_balance_validation_consistency() {
             &invalid_amount,
             &description,
             &due_date,
+            &None,
         );
         assert!(
             invoice_result.is_err() || invoice_result.unwrap().is_err(),
@@ -0,0 +1,99 @@
+//! Property-based tests for batch payouts
+//! Feature: stellar-smart-contract-dapp, Property 27: Batch Payout Atomicity
+//! **Validates: Requirements 1.1, 1.2, 1.3**
+
+use crate::payout::MAX_BATCH_PAYOUT;
+use crate::testutils::assert_within_tx_limits;
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, PayoutEntry, TransactionType,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Build `count` payout entries, making the entry at `invalid_at` zero-valued
+fn payout_entries(env: &Env, count: u32, invalid_at: Option<u32>) -> Vec<PayoutEntry> {
+    let mut entries = Vec::new(env);
+    for n in 0..count {
+        entries.push_back(PayoutEntry {
+            recipient: Address::generate(env),
+            amount: if invalid_at == Some(n) { 0 } else { 100 + n as i128 },
+            memo: SorobanString::from_str(env, "Weekly payout"),
+        });
+    }
+    entries
+}
+
+/// Property 27: Batch Payout Atomicity
+///
+/// For any batch of payout entries, a valid batch SHALL record exactly one
+/// transaction per entry in order, and a batch with any invalid entry SHALL
+/// record none.
+///
+/// This test runs 100 iterations with batches of 1 to `MAX_BATCH_PAYOUT`
+/// entries, every third batch containing one invalid entry.
+#[test]
+fn property_batch_payout_atomicity() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let sender = Address::generate(&env);
+        let count = 1 + (iteration as u32 % MAX_BATCH_PAYOUT);
+        let invalid_at = if iteration % 3 == 0 { Some(iteration as u32 % count) } else { None };
+        let entries = payout_entries(&env, count, invalid_at);
+
+        let result = client.try_execute_batch_payout(&sender, &entries);
+        if invalid_at.is_some() {
+            assert_eq!(
+                result,
+                Err(Ok(ContractError::InvalidAmount)),
+                "Iteration {}: An invalid entry should reject the batch",
+                iteration
+            );
+            assert!(client.try_get_transaction(&1).is_err());
+            continue;
+        }
+
+        let transaction_ids = result.unwrap().unwrap();
+        assert_eq!(transaction_ids.len(), count);
+        for (entry, tx_id) in entries.iter().zip(transaction_ids.iter()) {
+            let transaction = client.get_transaction(&tx_id);
+            assert_eq!(transaction.transaction_type, TransactionType::Basic);
+            assert_eq!(transaction.sender, sender);
+            assert_eq!(transaction.recipient, entry.recipient);
+            assert_eq!(transaction.amount, entry.amount);
+        }
+    }
+}
+
+/// A full batch of `MAX_BATCH_PAYOUT` legs fits the per-transaction budget,
+/// and one more leg is rejected
+#[test]
+fn test_max_batch_fits_resource_budget() {
+    let (env, _contract_id, client) = setup_test_env();
+    let sender = Address::generate(&env);
+
+    let entries = payout_entries(&env, MAX_BATCH_PAYOUT, None);
+    client.execute_batch_payout(&sender, &entries);
+    // One transaction entry per leg plus contract instance storage
+    assert!(env.cost_estimate().resources().write_entries > MAX_BATCH_PAYOUT);
+    assert_within_tx_limits(&env);
+
+    let oversized = payout_entries(&env, MAX_BATCH_PAYOUT + 1, None);
+    assert_eq!(
+        client.try_execute_batch_payout(&sender, &oversized),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+}
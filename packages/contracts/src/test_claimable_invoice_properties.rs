@@ -0,0 +1,161 @@
+//! Property-based tests for invoices claimable by approval key
+//! Feature: stellar-smart-contract-dapp, Property 17: Key-Bound Invoice Claiming
+//! **Validates: Requirements 4.1, 4.2**
+
+use crate::{StellarDAppContract, StellarDAppContractClient, ContractError, InvoiceStatus};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{testutils::Address as _, xdr::ToXdr, Address, BytesN, Env, String as SorobanString};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Derive an approval keypair from a seed, returning it with its public key
+fn claim_keypair(env: &Env, seed: [u8; 32]) -> (SigningKey, BytesN<32>) {
+    let key = SigningKey::from_bytes(&seed);
+    let public_key = BytesN::from_array(env, &key.verifying_key().to_bytes());
+    (key, public_key)
+}
+
+/// Sign a claim of `invoice_id` by `claimant` the way the contract verifies it
+fn sign_claim(
+    env: &Env,
+    key: &SigningKey,
+    contract_id: &Address,
+    invoice_id: u64,
+    claimant: &Address,
+) -> BytesN<64> {
+    let message = (contract_id.clone(), invoice_id, claimant.clone()).to_xdr(env);
+    let mut buf = [0u8; 256];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut buf[..len]);
+    BytesN::from_array(env, &key.sign(&buf[..len]).to_bytes())
+}
+
+/// Property 17: Key-Bound Invoice Claiming
+///
+/// For any invoice created against an approval key, only a wallet
+/// presenting a signature of its own address by that key SHALL become its
+/// client, only the first such wallet SHALL be bound, and the invoice SHALL
+/// NOT be approvable before it is claimed.
+///
+/// This test runs 100 iterations with varied approval keys and amounts.
+#[test]
+fn property_key_bound_invoice_claiming() {
+    for iteration in 0..100u64 {
+        let (env, contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let claimant = Address::generate(&env);
+        let latecomer = Address::generate(&env);
+
+        let mut seed = [0u8; 32];
+        seed[..8].copy_from_slice(&iteration.to_be_bytes());
+        seed[31] = 0xA5;
+        let (key, claim_key) = claim_keypair(&env, seed);
+
+        let amount: i128 = 750 + (iteration as i128 * 40);
+        let due_date = env.ledger().timestamp() + 5_000;
+        let description = SorobanString::from_str(&env, "Invoice sent by email");
+
+        let invoice_id = client
+            .create_claimable_invoice(&creator, &claim_key, &amount, &description, &due_date)
+            .invoice_id;
+        client.mark_invoice_sent(&invoice_id, &creator);
+        assert_eq!(client.get_invoice(&invoice_id).client, None);
+
+        // Unclaimed invoices cannot be approved or rejected
+        assert_eq!(
+            client.try_approve_invoice(&invoice_id, &claimant, &1),
+            Err(Ok(ContractError::InvoiceUnclaimed)),
+            "Iteration {}: Unclaimed invoice should not be approvable",
+            iteration
+        );
+
+        // A wrong key fails signature verification in the host
+        let mut wrong_seed = seed;
+        wrong_seed[31] ^= 0xFF;
+        let (wrong_key, _) = claim_keypair(&env, wrong_seed);
+        assert!(
+            matches!(
+                client.try_claim_invoice(&invoice_id, &latecomer, &sign_claim(&env, &wrong_key, &contract_id, invoice_id, &latecomer)),
+                Err(Err(_))
+            ),
+            "Iteration {}: Wrong key should be rejected",
+            iteration
+        );
+
+        // A claim seen in flight cannot be replayed by another wallet
+        let signature = sign_claim(&env, &key, &contract_id, invoice_id, &claimant);
+        assert!(
+            matches!(client.try_claim_invoice(&invoice_id, &latecomer, &signature), Err(Err(_))),
+            "Iteration {}: Signature should only bind the signed claimant",
+            iteration
+        );
+        assert_eq!(client.get_invoice(&invoice_id).client, None);
+
+        // The first wallet with a signature of its address becomes the client
+        let claimed = client.claim_invoice(&invoice_id, &claimant, &signature);
+        assert_eq!(claimed.status, InvoiceStatus::Sent);
+        assert_eq!(client.get_invoice(&invoice_id).client, Some(claimant.clone()));
+
+        // Later presenters cannot rebind it
+        assert_eq!(
+            client.try_claim_invoice(&invoice_id, &latecomer, &sign_claim(&env, &key, &contract_id, invoice_id, &latecomer)),
+            Err(Ok(ContractError::InvoiceAlreadyClaimed)),
+            "Iteration {}: Claimed invoice should not be rebound",
+            iteration
+        );
+        assert_eq!(
+            client.try_approve_invoice(&invoice_id, &latecomer, &1),
+            Err(Ok(ContractError::Unauthorized))
+        );
+
+        // The bound client completes the normal workflow
+        if iteration % 2 == 0 {
+            client.approve_invoice(&invoice_id, &claimant, &1);
+            let executed = client.execute_invoice(&invoice_id);
+            assert_eq!(executed.status, InvoiceStatus::Executed);
+        } else {
+            let reason = SorobanString::from_str(&env, "Not my order");
+            let rejected = client.reject_invoice(&invoice_id, &claimant, &reason);
+            assert_eq!(rejected.status, InvoiceStatus::Rejected);
+        }
+    }
+}
+
+/// The holdback arbiter of a claimable invoice cannot claim it and become
+/// a party to its own dispute
+#[test]
+fn test_arbiter_cannot_claim_invoice() {
+    let (env, contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let claimant = Address::generate(&env);
+    let (key, claim_key) = claim_keypair(&env, [9; 32]);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Catering");
+
+    let invoice_id = client
+        .create_claimable_invoice(&creator, &claim_key, &400, &description, &due_date)
+        .invoice_id;
+    client.set_invoice_holdback(&invoice_id, &creator, &3_600, &arbiter);
+
+    assert_eq!(
+        client.try_claim_invoice(&invoice_id, &arbiter, &sign_claim(&env, &key, &contract_id, invoice_id, &arbiter)),
+        Err(Ok(ContractError::InvalidArbiter))
+    );
+    client.claim_invoice(&invoice_id, &claimant, &sign_claim(&env, &key, &contract_id, invoice_id, &claimant));
+    assert_eq!(client.get_invoice(&invoice_id).client, Some(claimant));
+}
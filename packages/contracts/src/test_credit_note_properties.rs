@@ -0,0 +1,243 @@
+//! Property-based tests for credit notes against executed invoices
+//! Feature: stellar-smart-contract-dapp, Property 15: Credit Note Bounds
+//! **Validates: Requirements 4.3**
+
+use crate::testutils::{MockPriceOracle, MockPriceOracleClient};
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, CreditDisposition,
+    CreditNoteStatus, TransactionType,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, String as SorobanString};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Create, send, approve and execute an invoice, returning its ID
+fn executed_invoice(
+    env: &Env,
+    client: &StellarDAppContractClient,
+    creator: &Address,
+    client_addr: &Address,
+    amount: i128,
+) -> u64 {
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(env, "Goods");
+    let invoice_id = client
+        .create_invoice(creator, client_addr, &amount, &description, &due_date, &None)
+        .invoice_id;
+    client.mark_invoice_sent(&invoice_id, creator);
+    client.approve_invoice(&invoice_id, client_addr, &1);
+    client.execute_invoice(&invoice_id);
+    invoice_id
+}
+
+/// Property 15: Credit Note Bounds
+///
+/// For any executed invoice and any sequence of credit notes, the total
+/// credited SHALL never exceed the amount paid, and the net settled amount
+/// SHALL always equal the amount paid minus the amount credited.
+///
+/// This test runs 100 iterations with varied invoice and credit amounts.
+#[test]
+fn property_credit_note_bounds() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+        let amount: i128 = 1_000 + (iteration as i128 * 137);
+        let invoice_id = executed_invoice(&env, &client, &creator, &client_addr, amount);
+
+        assert_eq!(client.get_invoice(&invoice_id).paid_amount, amount);
+        assert_eq!(client.get_invoice_net_settled(&invoice_id), amount);
+
+        // Issue credits of a varied size until the paid amount is exhausted
+        let credit: i128 = 1 + amount / (2 + (iteration as i128 % 5));
+        let reason = SorobanString::from_str(&env, "Returned goods");
+        let mut credited: i128 = 0;
+        let mut issued = 0u32;
+        while credited + credit <= amount {
+            let disposition = if issued % 2 == 0 {
+                CreditDisposition::Refund
+            } else {
+                CreditDisposition::ApplyToNextInvoice
+            };
+            client.issue_credit_note(&creator, &invoice_id, &credit, &reason, &disposition);
+            credited += credit;
+            issued += 1;
+
+            assert_eq!(
+                client.get_invoice_net_settled(&invoice_id),
+                amount - credited,
+                "Iteration {}: Net settled should equal paid minus credited",
+                iteration
+            );
+        }
+
+        // Any credit beyond the paid amount is rejected
+        assert_eq!(
+            client.try_issue_credit_note(
+                &creator,
+                &invoice_id,
+                &(amount - credited + 1),
+                &reason,
+                &CreditDisposition::Refund,
+            ),
+            Err(Ok(ContractError::CreditExceedsPayment)),
+            "Iteration {}: Credits must not exceed the amount paid",
+            iteration
+        );
+
+        let invoice = client.get_invoice(&invoice_id);
+        assert_eq!(invoice.credited_amount, credited);
+        assert!(invoice.credited_amount <= invoice.paid_amount);
+        assert_eq!(client.get_invoice_credit_notes(&invoice_id).len(), issued);
+    }
+}
+
+/// Refund credits create a transaction back to the client, while deferred
+/// credits reduce what the client pays on the next invoice.
+#[test]
+fn test_credit_note_refund_and_application() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let reason = SorobanString::from_str(&env, "Damaged item");
+    let first = executed_invoice(&env, &client, &creator, &client_addr, 1_000);
+
+    // Refunded immediately
+    let refunded = client.issue_credit_note(
+        &creator,
+        &first,
+        &100i128,
+        &reason,
+        &CreditDisposition::Refund,
+    );
+    assert_eq!(refunded.status, CreditNoteStatus::Refunded);
+    let refund_tx_id = client
+        .get_credit_note(&refunded.credit_note_id)
+        .refund_tx_id
+        .unwrap();
+    let refund_tx = client.get_transaction(&refund_tx_id);
+    assert_eq!(refund_tx.transaction_type, TransactionType::Invoice);
+    assert_eq!(refund_tx.sender, creator);
+    assert_eq!(refund_tx.recipient, client_addr);
+    assert_eq!(refund_tx.amount, 100);
+
+    // Deferred credits are applied to the next invoice for the same client
+    let deferred = client.issue_credit_note(
+        &creator,
+        &first,
+        &250i128,
+        &reason,
+        &CreditDisposition::ApplyToNextInvoice,
+    );
+    assert_eq!(deferred.status, CreditNoteStatus::Pending);
+
+    // An invoice to another client is unaffected
+    let other_client = Address::generate(&env);
+    let unrelated = executed_invoice(&env, &client, &creator, &other_client, 400);
+    assert_eq!(client.get_invoice(&unrelated).paid_amount, 400);
+
+    // A smaller next invoice consumes part of the credit
+    let second = executed_invoice(&env, &client, &creator, &client_addr, 200);
+    assert_eq!(client.get_invoice(&second).paid_amount, 0);
+    let partially = client.get_credit_note(&deferred.credit_note_id);
+    assert_eq!(partially.remaining, 50);
+    assert_eq!(partially.status, CreditNoteStatus::Pending);
+
+    // The rest is consumed by the following invoice
+    let third = executed_invoice(&env, &client, &creator, &client_addr, 300);
+    assert_eq!(client.get_invoice(&third).paid_amount, 250);
+    let applied = client.get_credit_note(&deferred.credit_note_id);
+    assert_eq!(applied.remaining, 0);
+    assert_eq!(applied.status, CreditNoteStatus::Applied);
+
+    // Nothing was paid on the second invoice, so it cannot be credited
+    assert_eq!(
+        client.try_issue_credit_note(&creator, &second, &1i128, &reason, &CreditDisposition::Refund),
+        Err(Ok(ContractError::CreditExceedsPayment))
+    );
+
+    // Only the creator can issue credit notes
+    assert_eq!(
+        client.try_issue_credit_note(&client_addr, &third, &1i128, &reason, &CreditDisposition::Refund),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+/// Credits only apply to later invoices in the same denomination asset,
+/// and split invoices cannot be credited
+#[test]
+fn test_credits_are_kept_per_asset() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = StellarDAppContractClient::new(&env, &env.register(StellarDAppContract, ()));
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let usd = Address::generate(&env);
+    let xlm = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Goods");
+    let reason = SorobanString::from_str(&env, "Late delivery");
+
+    // Register a 1 USD = 10 XLM oracle
+    let oracle_id = env.register(MockPriceOracle, ());
+    client.initialize(&admin);
+    client.set_price_oracle(&admin, &oracle_id);
+    let oracle = MockPriceOracleClient::new(&env, &oracle_id);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&usd, &10_000_000, &now);
+    oracle.set_price(&xlm, &1_000_000, &now);
+
+    let usd_invoice = |amount: i128| {
+        let invoice_id = client
+            .create_invoice(&creator, &client_addr, &amount, &description, &due_date, &None)
+            .invoice_id;
+        client.set_invoice_pricing(&invoice_id, &creator, &usd, &xlm, &300);
+        client.approve_invoice_with_quote(&invoice_id, &client_addr, &2, &100);
+        client.execute_invoice(&invoice_id);
+        invoice_id
+    };
+
+    // A USD credit is not deducted from an unpriced invoice
+    let credited = usd_invoice(1_000);
+    let note = client.issue_credit_note(&creator, &credited, &300, &reason, &CreditDisposition::ApplyToNextInvoice);
+    assert_eq!(client.get_credit_note(&note.credit_note_id).asset, Some(usd.clone()));
+    let unpriced = executed_invoice(&env, &client, &creator, &client_addr, 1_000);
+    assert_eq!(client.get_invoice(&unpriced).paid_amount, 1_000);
+
+    // The next USD invoice takes it
+    let next = usd_invoice(1_000);
+    assert_eq!(client.get_invoice(&next).paid_amount, 700);
+    assert_eq!(client.get_credit_note(&note.credit_note_id).status, CreditNoteStatus::Applied);
+
+    // Split invoices have no single client to credit
+    let payer = Address::generate(&env);
+    let mut shares: Map<Address, i128> = Map::new(&env);
+    shares.set(payer.clone(), 500);
+    let split_id = client
+        .create_split_invoice(&creator, &shares, &description, &due_date)
+        .invoice_id;
+    client.mark_invoice_sent(&split_id, &creator);
+    client.approve_invoice_share(&split_id, &payer);
+    client.pay_invoice_share(&split_id, &payer);
+    assert_eq!(
+        client.try_issue_credit_note(&creator, &split_id, &100, &reason, &CreditDisposition::Refund),
+        Err(Ok(ContractError::SplitInvoiceNotCreditable))
+    );
+}
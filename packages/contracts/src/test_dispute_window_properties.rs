@@ -0,0 +1,271 @@
+//! Property-based tests for post-settlement dispute windows
+//! Feature: stellar-smart-contract-dapp, Property 20: Holdback Custody
+//! **Validates: Requirements 4.3, 4.4**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, CreditDisposition,
+    CreditNoteStatus, HoldbackStatus, InvoiceStatus,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 20: Holdback Custody
+///
+/// For any executed invoice with a holdback, no payout SHALL be recorded
+/// until the window ends or the arbiter rules. Undisputed funds SHALL be
+/// released to the creator after the window; disputed funds SHALL follow
+/// the arbiter's decision.
+///
+/// This test runs 100 iterations cycling through release, dispute won by
+/// the creator, and dispute won by the client.
+#[test]
+fn property_holdback_custody() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+        let amount: i128 = 2_000 + (iteration as i128 * 75);
+        let due_date = env.ledger().timestamp() + 10_000;
+        let window = 3_600 + iteration * 10;
+        let description = SorobanString::from_str(&env, "Site survey");
+
+        let invoice_id = client
+            .create_invoice(&creator, &client_addr, &amount, &description, &due_date, &None)
+            .invoice_id;
+        client.set_invoice_holdback(&invoice_id, &creator, &window, &arbiter);
+        client.mark_invoice_sent(&invoice_id, &creator);
+        client.approve_invoice(&invoice_id, &client_addr, &2);
+        client.execute_invoice(&invoice_id);
+
+        // Funds are held: the invoice is paid but no payout exists yet
+        let executed_at = env.ledger().timestamp();
+        let invoice = client.get_invoice(&invoice_id);
+        assert_eq!(invoice.status, InvoiceStatus::Executed);
+        assert_eq!(invoice.paid_amount, amount);
+        assert_eq!(invoice.settlement_tx_id, None);
+        assert_eq!(invoice.holdback_status, HoldbackStatus::Holding);
+        assert_eq!(invoice.holdback_arbiter, Some(arbiter.clone()));
+        assert_eq!(invoice.holdback_release_at, Some(executed_at + window));
+        assert_eq!(
+            client.try_release_invoice_holdback(&invoice_id),
+            Err(Ok(ContractError::DisputeWindowOpen))
+        );
+
+        match iteration % 3 {
+            0 => {
+                env.ledger().with_mut(|li| {
+                    li.timestamp = executed_at + window;
+                });
+                assert_eq!(
+                    client.try_raise_invoice_dispute(
+                        &invoice_id,
+                        &client_addr,
+                        &SorobanString::from_str(&env, "Too late")
+                    ),
+                    Err(Ok(ContractError::DisputeWindowClosed))
+                );
+
+                let invoice = client.release_invoice_holdback(&invoice_id);
+                assert_eq!(invoice.holdback_status, HoldbackStatus::Released);
+                let payout = client.get_transaction(&invoice.settlement_tx_id.unwrap());
+                assert_eq!(payout.sender, client_addr);
+                assert_eq!(payout.recipient, creator);
+                assert_eq!(payout.amount, amount);
+            }
+            outcome => {
+                let reason = SorobanString::from_str(&env, "Survey incomplete");
+                client.raise_invoice_dispute(&invoice_id, &client_addr, &reason);
+                let invoice = client.get_invoice(&invoice_id);
+                assert_eq!(invoice.holdback_status, HoldbackStatus::Disputed);
+                assert_eq!(invoice.dispute_reason, Some(reason));
+
+                // Disputed funds are frozen past the window until the arbiter rules
+                env.ledger().with_mut(|li| {
+                    li.timestamp = executed_at + window + 1;
+                });
+                assert_eq!(
+                    client.try_release_invoice_holdback(&invoice_id),
+                    Err(Ok(ContractError::HoldbackNotActive))
+                );
+                assert_eq!(
+                    client.try_resolve_invoice_dispute(&invoice_id, &creator, &true),
+                    Err(Ok(ContractError::Unauthorized))
+                );
+
+                let release_to_creator = outcome == 1;
+                let invoice = client.resolve_invoice_dispute(&invoice_id, &arbiter, &release_to_creator);
+                if release_to_creator {
+                    assert_eq!(invoice.status, InvoiceStatus::Executed);
+                    assert_eq!(invoice.holdback_status, HoldbackStatus::Released);
+                    let payout = client.get_transaction(&invoice.settlement_tx_id.unwrap());
+                    assert_eq!(payout.recipient, creator);
+                    assert_eq!(payout.amount, amount);
+                } else {
+                    assert_eq!(
+                        invoice.status,
+                        InvoiceStatus::Refunded,
+                        "Iteration {}: Client-favoured ruling should refund the invoice",
+                        iteration
+                    );
+                    assert_eq!(invoice.holdback_status, HoldbackStatus::Refunded);
+                    assert_eq!(invoice.paid_amount, 0);
+                    assert_eq!(invoice.settlement_tx_id, None);
+                }
+            }
+        }
+    }
+}
+
+/// Holdbacks can only be configured by the creator before approval
+#[test]
+fn test_holdback_requires_unapproved_invoice() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Consulting");
+
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &500, &description, &due_date, &None)
+        .invoice_id;
+    assert_eq!(
+        client.try_set_invoice_holdback(&invoice_id, &client_addr, &3_600, &arbiter),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_invoice_holdback(&invoice_id, &creator, &0, &arbiter),
+        Err(Ok(ContractError::InvalidSchedule))
+    );
+
+    client.approve_invoice(&invoice_id, &client_addr, &1);
+    assert_eq!(
+        client.try_set_invoice_holdback(&invoice_id, &creator, &3_600, &arbiter),
+        Err(Ok(ContractError::InvalidInvoiceStatus))
+    );
+
+    // Without a holdback, execution pays out immediately
+    client.execute_invoice(&invoice_id);
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.holdback_status, HoldbackStatus::None);
+    assert!(invoice.settlement_tx_id.is_some());
+}
+
+/// Holdbacks need an independent arbiter, change the approved terms, and
+/// keep held funds out of escrow and credit notes
+#[test]
+fn test_holdback_guards() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Renovation");
+
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &800, &description, &due_date, &None)
+        .invoice_id;
+    for party in [&creator, &client_addr] {
+        assert_eq!(
+            client.try_set_invoice_holdback(&invoice_id, &creator, &3_600, party),
+            Err(Ok(ContractError::InvalidArbiter))
+        );
+    }
+
+    client.mark_invoice_sent(&invoice_id, &creator);
+    client.set_invoice_holdback(&invoice_id, &creator, &3_600, &arbiter);
+    assert_eq!(
+        client.try_approve_invoice(&invoice_id, &client_addr, &1),
+        Err(Ok(ContractError::InvoiceVersionMismatch))
+    );
+    client.approve_invoice(&invoice_id, &client_addr, &2);
+
+    let conditions = Vec::new(&env);
+    assert_eq!(
+        client.try_fund_invoice_escrow(&invoice_id, &client_addr, &conditions, &due_date),
+        Err(Ok(ContractError::InvalidInvoiceStatus))
+    );
+
+    client.execute_invoice(&invoice_id);
+    assert_eq!(
+        client.try_issue_credit_note(
+            &creator,
+            &invoice_id,
+            &100,
+            &SorobanString::from_str(&env, "Goodwill"),
+            &CreditDisposition::Refund
+        ),
+        Err(Ok(ContractError::InvalidInvoiceStatus))
+    );
+
+    client.raise_invoice_dispute(&invoice_id, &client_addr, &SorobanString::from_str(&env, "Unfinished"));
+    let invoice = client.resolve_invoice_dispute(&invoice_id, &arbiter, &false);
+    assert_eq!(invoice.status, InvoiceStatus::Refunded);
+    assert_eq!(client.get_invoice_net_settled(&invoice_id), 0);
+}
+
+/// Refunding a disputed payment gives back the pending credit that was
+/// applied to it at execution
+#[test]
+fn test_dispute_refund_restores_applied_credit() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Landscaping");
+
+    let credited = client
+        .create_invoice(&creator, &client_addr, &1_000, &description, &due_date, &None)
+        .invoice_id;
+    client.mark_invoice_sent(&credited, &creator);
+    client.approve_invoice(&credited, &client_addr, &1);
+    client.execute_invoice(&credited);
+    let note = client.issue_credit_note(
+        &creator,
+        &credited,
+        &300,
+        &SorobanString::from_str(&env, "Missed visit"),
+        &CreditDisposition::ApplyToNextInvoice,
+    );
+
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &1_000, &description, &due_date, &None)
+        .invoice_id;
+    client.mark_invoice_sent(&invoice_id, &creator);
+    client.set_invoice_holdback(&invoice_id, &creator, &3_600, &arbiter);
+    client.approve_invoice(&invoice_id, &client_addr, &2);
+    client.execute_invoice(&invoice_id);
+    assert_eq!(client.get_invoice(&invoice_id).paid_amount, 700);
+    assert_eq!(client.get_credit_note(&note.credit_note_id).status, CreditNoteStatus::Applied);
+
+    client.raise_invoice_dispute(&invoice_id, &client_addr, &SorobanString::from_str(&env, "Unfinished"));
+    client.resolve_invoice_dispute(&invoice_id, &arbiter, &false);
+
+    // The next invoice is credited again
+    let next = client
+        .create_invoice(&creator, &client_addr, &1_000, &description, &due_date, &None)
+        .invoice_id;
+    client.mark_invoice_sent(&next, &creator);
+    client.approve_invoice(&next, &client_addr, &1);
+    client.execute_invoice(&next);
+    assert_eq!(client.get_invoice(&next).paid_amount, 700);
+}
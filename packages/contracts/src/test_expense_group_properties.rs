@@ -0,0 +1,166 @@
+//! Property-based tests for shared-expense groups
+//! Feature: stellar-smart-contract-dapp, Property 34: Group Settlement Clears Balances
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, TransactionLink, TransactionType,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 34: Group Settlement Clears Balances
+///
+/// For any sequence of group expenses, member balances SHALL sum to zero,
+/// and settlement SHALL record at most one fewer P2P transfer than there
+/// are members with a balance, moving exactly each member's net balance
+/// and leaving every balance cleared.
+///
+/// This test runs 100 iterations with groups of two to six members and up
+/// to eight expenses shared among varying participants.
+#[test]
+fn property_group_settlement_clears_balances() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let member_count = 2 + (iteration % 5) as u32;
+        let mut members: Vec<Address> = Vec::new(&env);
+        for _ in 0..member_count {
+            members.push_back(Address::generate(&env));
+        }
+        let creator = members.get(0).unwrap();
+        let group_id = client.create_expense_group(&creator, &members);
+
+        let expense_count = 1 + (iteration % 8) as u32;
+        for n in 0..expense_count {
+            let payer = members.get((n + iteration as u32) % member_count).unwrap();
+            let amount: i128 = 100 + (iteration as i128 * 31 + n as i128 * 17) % 900;
+            let mut participants: Vec<Address> = Vec::new(&env);
+            for (m, member) in members.iter().enumerate() {
+                if (m as u64 + n as u64 + iteration) % 3 != 0 {
+                    participants.push_back(member);
+                }
+            }
+            if participants.is_empty() {
+                participants.push_back(creator.clone());
+            }
+            client.record_group_expense(&group_id, &payer, &amount, &participants);
+        }
+
+        let group = client.get_expense_group(&group_id);
+        assert_eq!(group.expense_count, expense_count);
+        let total: i128 = group.balances.values().iter().sum();
+        assert_eq!(total, 0, "Iteration {}: Group balances should sum to zero", iteration);
+
+        let transaction_ids = client.settle_expense_group(&group_id);
+        assert!(transaction_ids.len() < group.balances.len().max(1));
+
+        // Replaying the transfers clears every balance
+        let mut remaining: Map<Address, i128> = group.balances.clone();
+        for transaction_id in transaction_ids.iter() {
+            let transaction = client.get_transaction(&transaction_id);
+            assert_eq!(transaction.transaction_type, TransactionType::P2P);
+            assert_eq!(transaction.link, TransactionLink::ExpenseGroup(group_id));
+            assert!(transaction.amount > 0);
+            let from = remaining.get(transaction.sender.clone()).unwrap();
+            let to = remaining.get(transaction.recipient.clone()).unwrap();
+            remaining.set(transaction.sender, from + transaction.amount);
+            remaining.set(transaction.recipient, to - transaction.amount);
+        }
+        for balance in remaining.values().iter() {
+            assert_eq!(balance, 0, "Iteration {}: Settlement should clear every balance", iteration);
+        }
+
+        let settled = client.get_expense_group(&group_id);
+        assert!(settled.balances.is_empty());
+        assert_eq!(settled.settlement_count, 1);
+    }
+}
+
+/// Only members can record or take part in expenses
+#[test]
+fn test_expenses_are_limited_to_members() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let mut members: Vec<Address> = Vec::new(&env);
+    members.push_back(bob.clone());
+    members.push_back(carol.clone());
+    let group_id = client.create_expense_group(&alice, &members);
+    assert_eq!(client.get_expense_group(&group_id).members.len(), 3);
+
+    let mut participants: Vec<Address> = Vec::new(&env);
+    participants.push_back(bob.clone());
+    assert_eq!(
+        client.try_record_group_expense(&group_id, &outsider, &300, &participants),
+        Err(Ok(ContractError::NotGroupMember))
+    );
+    participants.push_back(outsider.clone());
+    assert_eq!(
+        client.try_record_group_expense(&group_id, &alice, &300, &participants),
+        Err(Ok(ContractError::NotGroupMember))
+    );
+
+    // The remainder of 100 / 3 falls on the first participant
+    let mut everyone: Vec<Address> = Vec::new(&env);
+    everyone.push_back(alice.clone());
+    everyone.push_back(bob.clone());
+    everyone.push_back(carol.clone());
+    client.record_group_expense(&group_id, &alice, &300, &everyone);
+    let group = client.record_group_expense(&group_id, &bob, &100, &everyone);
+    assert_eq!(group.balances.get(alice.clone()), Some(166));
+    assert_eq!(group.balances.get(bob.clone()), Some(-33));
+    assert_eq!(group.balances.get(carol.clone()), Some(-133));
+
+    assert_eq!(client.settle_expense_group(&group_id).len(), 2);
+    assert_eq!(client.settle_expense_group(&group_id).len(), 0);
+}
+
+/// Balances that would overflow reject the expense instead of wrapping
+#[test]
+fn test_expense_balance_overflow_is_rejected() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let mut members: Vec<Address> = Vec::new(&env);
+    members.push_back(bob.clone());
+    members.push_back(carol.clone());
+    let group_id = client.create_expense_group(&alice, &members);
+
+    let mut participants: Vec<Address> = Vec::new(&env);
+    participants.push_back(bob.clone());
+    let amount = i128::MAX / 2;
+    client.record_group_expense(&group_id, &alice, &amount, &participants);
+    client.record_group_expense(&group_id, &alice, &amount, &participants);
+    assert_eq!(
+        client.try_record_group_expense(&group_id, &alice, &amount, &participants),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    // A debt of exactly i128::MIN could never be negated for settlement
+    assert_eq!(
+        client.try_record_group_expense(&group_id, &carol, &2, &participants),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+
+    let group = client.get_expense_group(&group_id);
+    assert_eq!(group.balances.get(alice.clone()), Some(amount * 2));
+    assert_eq!(group.balances.get(bob.clone()), Some(-amount * 2));
+    assert_eq!(group.expense_count, 2);
+}
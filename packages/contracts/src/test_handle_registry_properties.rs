@@ -0,0 +1,204 @@
+//! Property-based tests for the handle registry
+//! Feature: stellar-smart-contract-dapp, Property 36: Case-Insensitive Handle Resolution
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, HandlePayoutEntry, SplitShare,
+    TransactionLink, TransactionStatus,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 36: Case-Insensitive Handle Resolution
+///
+/// For any handle, every casing of it SHALL resolve to the same registered
+/// record, no other owner SHALL be able to claim any casing of it, and
+/// payments to it SHALL reach the address it resolves to at execution time
+/// while recording the normalized handle.
+///
+/// This test runs 100 iterations with handles of varying length and case.
+#[test]
+fn property_case_insensitive_handle_resolution() {
+    let alphabet = b"abcdefghijklmnopqrstuvwxyz0123456789_";
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let owner = Address::generate(&env);
+        let rival = Address::generate(&env);
+        let sender = Address::generate(&env);
+
+        // Build a handle of 3 to 20 characters and an alternately-cased copy
+        let len = 3 + (iteration % 18) as usize;
+        let mut lower = [0u8; 20];
+        let mut mixed = [0u8; 20];
+        for n in 0..len {
+            let byte = alphabet[(iteration as usize * 7 + n * 13) % alphabet.len()];
+            lower[n] = byte;
+            mixed[n] = if n % 2 == 0 { byte.to_ascii_uppercase() } else { byte };
+        }
+        let lower = SorobanString::from_bytes(&env, &lower[..len]);
+        let mixed = SorobanString::from_bytes(&env, &mixed[..len]);
+
+        let normalized = client.register_handle(&owner, &mixed);
+        assert_eq!(normalized, lower, "Iteration {}: Handles should be stored in lowercase", iteration);
+        assert_eq!(client.resolve_handle(&lower), owner);
+        assert_eq!(client.resolve_handle(&mixed), owner);
+        assert_eq!(
+            client.try_register_handle(&rival, &lower),
+            Err(Ok(ContractError::HandleTaken))
+        );
+
+        let amount: i128 = 100 + iteration as i128;
+        let memo = SorobanString::from_str(&env, "Lunch");
+        let result = client.execute_p2p_to_handle(&sender, &mixed, &amount, &memo);
+        let transaction = client.get_transaction(&result.transaction_id);
+        assert_eq!(transaction.recipient, owner);
+        assert_eq!(transaction.metadata, memo);
+        assert_eq!(transaction.link, TransactionLink::Handle(lower.clone()));
+
+        // Repointing the handle redirects later payments only
+        let new_target = Address::generate(&env);
+        client.update_handle(&mixed, &owner, &new_target);
+        let pending = client.send_pending_p2p_to_handle(&sender, &lower, &amount, &memo, &1_000);
+        assert_eq!(pending.status, TransactionStatus::Pending);
+        assert_eq!(client.get_transaction(&pending.transaction_id).recipient, new_target);
+        assert_eq!(client.get_transaction(&result.transaction_id).recipient, owner);
+    }
+}
+
+/// Only the owner can update or release a handle, and released handles
+/// can be claimed again
+#[test]
+fn test_handle_ownership_and_release() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let owner = Address::generate(&env);
+    let rival = Address::generate(&env);
+    let handle = SorobanString::from_str(&env, "Coffee_Shop");
+
+    for invalid in ["ab", "has space", "dash-ed", "averyveryveryveryverylonghandle_x"] {
+        assert_eq!(
+            client.try_register_handle(&owner, &SorobanString::from_str(&env, invalid)),
+            Err(Ok(ContractError::InvalidHandle))
+        );
+    }
+
+    client.register_handle(&owner, &handle);
+    assert_eq!(
+        client.try_update_handle(&handle, &rival, &rival),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_release_handle(&handle, &rival),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.release_handle(&SorobanString::from_str(&env, "coffee_shop"), &owner);
+    assert_eq!(
+        client.try_resolve_handle(&handle),
+        Err(Ok(ContractError::HandleNotFound))
+    );
+    assert_eq!(
+        client.try_execute_p2p_to_handle(&owner, &handle, &10, &SorobanString::from_str(&env, "Tip")),
+        Err(Ok(ContractError::HandleNotFound))
+    );
+
+    client.register_handle(&rival, &handle);
+    assert_eq!(client.get_handle(&handle).owner, rival);
+}
+
+/// Scheduled payments, batch payouts, split shares and payment requests
+/// addressed to a handle use wherever it points when they execute, but
+/// never follow it to a new owner
+#[test]
+fn test_handle_variants_resolve_at_execution() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let owner = Address::generate(&env);
+    let new_target = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let other = Address::generate(&env);
+    let handle = SorobanString::from_str(&env, "Bakery");
+    let normalized = client.register_handle(&owner, &handle);
+    let memo = SorobanString::from_str(&env, "Bread");
+    let now = env.ledger().timestamp();
+
+    // Scheduled payment: resolved again at execution
+    let payment_id = client.schedule_payment_to_handle(&sender, &handle, &250, &memo, &(now + 100));
+    let late_payment_id = client.schedule_payment_to_handle(&sender, &handle, &250, &memo, &(now + 100));
+    let payment = client.get_scheduled_payment(&payment_id);
+    assert_eq!(payment.recipient_handle, Some(normalized.clone()));
+    assert_eq!(payment.recipient_handle_owner, Some(owner.clone()));
+
+    // Split share: resolved again at every payment
+    let mut shares: Vec<SplitShare> = Vec::new(&env);
+    shares.push_back(SplitShare { recipient: sender.clone(), bps: 6_000, handle: Some(handle.clone()), handle_owner: None });
+    shares.push_back(SplitShare { recipient: other.clone(), bps: 4_000, handle: None, handle_owner: None });
+    let split_id = client.create_split_config(&owner, &shares);
+    let share = client.get_split_config(&split_id).shares.get_unchecked(0);
+    assert_eq!(share.recipient, owner);
+    assert_eq!(share.handle_owner, Some(owner.clone()));
+
+    client.update_handle(&handle, &owner, &new_target);
+
+    // Payment request: addressed to the current target
+    let request_id = client.create_payment_request_to_handle(&other, &handle, &40, &memo);
+    let request = client.get_payment_request(&request_id);
+    assert_eq!(request.payer, new_target);
+    assert_eq!(request.payer_handle, Some(normalized.clone()));
+    client.pay_payment_request(&request_id, &new_target);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = now + 100;
+    });
+    let scheduled = client.execute_scheduled_payment(&payment_id);
+    assert_eq!(client.get_transaction(&scheduled.transaction_id).recipient, new_target);
+
+    let legs = client.execute_p2p_split_transaction(&sender, &split_id, &1_000, &memo);
+    assert_eq!(client.get_transaction(&legs.get_unchecked(0)).recipient, new_target);
+    assert_eq!(client.get_transaction(&legs.get_unchecked(1)).recipient, other);
+
+    // Batch payout: each leg records the handle it was sent to
+    let mut entries: Vec<HandlePayoutEntry> = Vec::new(&env);
+    entries.push_back(HandlePayoutEntry { handle: handle.clone(), amount: 75, memo: memo.clone() });
+    let payout_ids = client.execute_batch_payout_to_handles(&sender, &entries);
+    let payout = client.get_transaction(&payout_ids.get_unchecked(0));
+    assert_eq!(payout.recipient, new_target);
+    assert_eq!(payout.link, TransactionLink::Handle(normalized));
+
+    // Released handles no longer resolve
+    client.release_handle(&handle, &owner);
+    assert_eq!(
+        client.try_execute_p2p_split_transaction(&sender, &split_id, &1_000, &memo),
+        Err(Ok(ContractError::HandleNotFound))
+    );
+    assert_eq!(
+        client.try_execute_batch_payout_to_handles(&sender, &entries),
+        Err(Ok(ContractError::HandleNotFound))
+    );
+
+    // Nor do payments locked earlier follow the handle to a new owner
+    let squatter = Address::generate(&env);
+    client.register_handle(&squatter, &handle);
+    assert_eq!(
+        client.try_execute_scheduled_payment(&late_payment_id),
+        Err(Ok(ContractError::HandleNotFound))
+    );
+    assert_eq!(
+        client.try_execute_p2p_split_transaction(&sender, &split_id, &1_000, &memo),
+        Err(Ok(ContractError::HandleNotFound))
+    );
+}
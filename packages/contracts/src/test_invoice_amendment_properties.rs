@@ -0,0 +1,136 @@
+//! Property-based tests for invoice amendment and cancellation
+//! Feature: stellar-smart-contract-dapp, Property 13: Versioned Invoice Approval
+//! **Validates: Requirements 4.2, 4.3**
+
+use crate::{StellarDAppContract, StellarDAppContractClient, ContractError, InvoiceStatus};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 13: Versioned Invoice Approval
+///
+/// For any sequence of amendments to an unpaid invoice, the version SHALL
+/// increase by one per amendment, any earlier approval SHALL be invalidated,
+/// and only an approval of the current version SHALL be accepted.
+///
+/// This test runs 100 iterations with varied amounts and amendment counts.
+#[test]
+fn property_versioned_invoice_approval() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+
+        let amount: i128 = 1_000 + (iteration as i128 * 750);
+        let due_date = env.ledger().timestamp() + 10_000;
+        let description = SorobanString::from_str(&env, "Consulting");
+
+        let invoice_id = client
+            .create_invoice(&creator, &client_addr, &amount, &description, &due_date, &None)
+            .invoice_id;
+        client.mark_invoice_sent(&invoice_id, &creator);
+
+        // Approve the first version, then amend it one or more times
+        client.approve_invoice(&invoice_id, &client_addr, &1);
+        let amendments = 1 + (iteration % 3) as u32;
+        let mut final_amount = amount;
+        for n in 0..amendments {
+            final_amount = amount + (n as i128 + 1) * 10;
+            let amended = client.amend_invoice(
+                &invoice_id,
+                &creator,
+                &final_amount,
+                &SorobanString::from_str(&env, "Consulting (revised)"),
+                &(due_date + 100),
+            );
+            assert_eq!(
+                amended.status,
+                InvoiceStatus::Sent,
+                "Iteration {}: Amendment should invalidate any approval",
+                iteration
+            );
+        }
+
+        let invoice = client.get_invoice(&invoice_id);
+        assert_eq!(invoice.version, 1 + amendments);
+        assert_eq!(invoice.amount, final_amount);
+        assert_eq!(invoice.approved_at, None);
+
+        // Approvals of any earlier version are rejected
+        for stale in 1..invoice.version {
+            assert_eq!(
+                client.try_approve_invoice(&invoice_id, &client_addr, &stale),
+                Err(Ok(ContractError::InvoiceVersionMismatch)),
+                "Iteration {}: Approval of version {} should be rejected",
+                iteration, stale
+            );
+        }
+
+        // The current version can be approved and paid
+        let approved = client.approve_invoice(&invoice_id, &client_addr, &invoice.version);
+        assert_eq!(approved.status, InvoiceStatus::Approved);
+        let executed = client.execute_invoice(&invoice_id);
+        assert_eq!(executed.status, InvoiceStatus::Executed);
+
+        // Paid invoices can no longer be amended
+        assert_eq!(
+            client.try_amend_invoice(&invoice_id, &creator, &amount, &description, &due_date),
+            Err(Ok(ContractError::InvalidInvoiceStatus))
+        );
+    }
+}
+
+/// Creators can cancel unpaid invoices; cancelled invoices cannot be
+/// approved, executed or amended.
+#[test]
+fn test_invoice_cancellation() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 1_000;
+    let description = SorobanString::from_str(&env, "Goods");
+
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &5_000i128, &description, &due_date, &None)
+        .invoice_id;
+    client.mark_invoice_sent(&invoice_id, &creator);
+    client.approve_invoice(&invoice_id, &client_addr, &1);
+
+    // Only the creator can cancel
+    assert_eq!(
+        client.try_cancel_invoice(&invoice_id, &client_addr),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    let cancelled = client.cancel_invoice(&invoice_id, &creator);
+    assert_eq!(cancelled.status, InvoiceStatus::Cancelled);
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Cancelled);
+
+    assert!(client.try_execute_invoice(&invoice_id).is_err());
+    assert!(client.try_approve_invoice(&invoice_id, &client_addr, &1).is_err());
+    assert_eq!(
+        client.try_cancel_invoice(&invoice_id, &creator),
+        Err(Ok(ContractError::InvalidInvoiceStatus))
+    );
+
+    // Cancelled invoices are not expired later on
+    env.ledger().with_mut(|li| {
+        li.timestamp = due_date + 1;
+    });
+    let checked = client.check_invoice_expiration(&invoice_id);
+    assert_eq!(checked.status, InvoiceStatus::Cancelled);
+}
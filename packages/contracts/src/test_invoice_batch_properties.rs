@@ -0,0 +1,185 @@
+//! Property-based tests for bulk invoice creation and expiration sweeps
+//! Feature: stellar-smart-contract-dapp, Property 22: Batch Invoice Lifecycle
+//! **Validates: Requirements 4.1, 4.5**
+
+use crate::batch::{MAX_INVOICE_BATCH, MAX_SWEEP_ITEMS};
+use crate::testutils::assert_within_tx_limits;
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, InvoiceSpec, InvoiceStatus,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 22: Batch Invoice Lifecycle
+///
+/// For any batch of invoice specs, batch creation SHALL create one draft
+/// invoice per spec in order, and repeated sweeps following the returned
+/// cursor SHALL expire exactly the overdue `Sent` or `Approved` invoices.
+///
+/// This test runs 100 iterations with batches of 1 to 10 invoices and
+/// sweep pages of 1 to 5 IDs.
+#[test]
+fn property_batch_invoice_lifecycle() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+        let batch_size = 1 + iteration as u32 % MAX_INVOICE_BATCH;
+        let now = env.ledger().timestamp();
+
+        let mut specs: Vec<InvoiceSpec> = Vec::new(&env);
+        for n in 0..batch_size {
+            specs.push_back(InvoiceSpec {
+                client: client_addr.clone(),
+                amount: 100 + n as i128,
+                description: SorobanString::from_str(&env, "Monthly subscription"),
+                // Alternate between short and long due dates
+                due_date: now + if n % 2 == 0 { 100 } else { 10_000 },
+                external_ref: None,
+            });
+        }
+
+        let invoice_ids = client.create_invoices_batch(&creator, &specs);
+        assert_eq!(invoice_ids.len(), batch_size);
+        for (n, invoice_id) in invoice_ids.iter().enumerate() {
+            let invoice = client.get_invoice(&invoice_id);
+            assert_eq!(invoice.status, InvoiceStatus::Draft);
+            assert_eq!(invoice.amount, 100 + n as i128);
+            // Send every invoice except every third one, which stays a draft
+            if n % 3 != 2 {
+                client.mark_invoice_sent(&invoice_id, &creator);
+            }
+        }
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = now + 101;
+        });
+
+        let page = 1 + (iteration % 5) as u32;
+        let mut cursor = Some(1u64);
+        let mut expired: Vec<u64> = Vec::new(&env);
+        while let Some(start_id) = cursor {
+            let result = client.sweep_expired_invoices(&start_id, &page);
+            assert!(result.expired.len() <= page);
+            expired.append(&result.expired);
+            cursor = result.next_id;
+        }
+
+        for (n, invoice_id) in invoice_ids.iter().enumerate() {
+            let should_expire = n % 2 == 0 && n % 3 != 2;
+            assert_eq!(
+                expired.contains(invoice_id),
+                should_expire,
+                "Iteration {}: Invoice {} sweep result mismatch",
+                iteration,
+                invoice_id
+            );
+            let status = client.get_invoice(&invoice_id).status;
+            assert_eq!(status == InvoiceStatus::Expired, should_expire);
+        }
+    }
+}
+
+/// A batch containing an invalid spec creates nothing
+#[test]
+fn test_invalid_batch_is_rejected_atomically() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 1_000;
+
+    let mut specs: Vec<InvoiceSpec> = Vec::new(&env);
+    for amount in [500i128, 0] {
+        specs.push_back(InvoiceSpec {
+            client: client_addr.clone(),
+            amount,
+            description: SorobanString::from_str(&env, "Batch item"),
+            due_date,
+            external_ref: None,
+        });
+    }
+
+    assert_eq!(
+        client.try_create_invoices_batch(&creator, &specs),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(client.try_get_invoice(&1), Err(Ok(ContractError::InvoiceNotFound)));
+    assert_eq!(
+        client.try_create_invoices_batch(&creator, &Vec::new(&env)),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+}
+
+/// A full batch with external references and a full sweep page of expiring
+/// invoices each fit the per-transaction budget, and larger ones are rejected
+#[test]
+fn test_max_batch_and_sweep_fit_resource_budget() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 100;
+    let references = ["ref-0", "ref-1", "ref-2", "ref-3", "ref-4", "ref-5", "ref-6", "ref-7", "ref-8", "ref-9", "ref-10"];
+
+    let mut specs: Vec<InvoiceSpec> = Vec::new(&env);
+    for reference in references.iter() {
+        specs.push_back(InvoiceSpec {
+            client: client_addr.clone(),
+            amount: 100,
+            description: SorobanString::from_str(&env, "Batch item"),
+            due_date,
+            external_ref: Some(SorobanString::from_str(&env, reference)),
+        });
+    }
+    assert_eq!(
+        client.try_create_invoices_batch(&creator, &specs),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    specs.pop_back();
+    assert_eq!(specs.len(), MAX_INVOICE_BATCH);
+
+    client.create_invoices_batch(&creator, &specs);
+    assert_within_tx_limits(&env);
+
+    // Fill a whole sweep page with overdue invoices
+    let mut plain: Vec<InvoiceSpec> = Vec::new(&env);
+    for _ in 0..MAX_INVOICE_BATCH {
+        plain.push_back(InvoiceSpec {
+            client: client_addr.clone(),
+            amount: 100,
+            description: SorobanString::from_str(&env, "Batch item"),
+            due_date,
+            external_ref: None,
+        });
+    }
+    for _ in 0..MAX_SWEEP_ITEMS / MAX_INVOICE_BATCH {
+        client.create_invoices_batch(&creator, &plain);
+    }
+    for invoice_id in 1..=MAX_SWEEP_ITEMS as u64 {
+        client.mark_invoice_sent(&invoice_id, &creator);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp = due_date + 1;
+    });
+
+    // Oversized pages are clamped rather than rejected
+    let result = client.sweep_expired_invoices(&1, &(MAX_SWEEP_ITEMS * 5));
+    assert_eq!(result.expired.len(), MAX_SWEEP_ITEMS);
+    assert_eq!(result.next_id, Some(MAX_SWEEP_ITEMS as u64 + 1));
+    assert_within_tx_limits(&env);
+}
@@ -0,0 +1,191 @@
+//! Property-based tests for funding invoices through escrow
+//! Feature: stellar-smart-contract-dapp, Property 19: Pay-on-Delivery Settlement
+//! **Validates: Requirements 2.1, 2.2, 4.3**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, Condition, ConditionType, ContractError,
+    CreditDisposition, EscrowStatus, InvoiceStatus,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 19: Pay-on-Delivery Settlement
+///
+/// For any approved invoice funded into an escrow, the escrow SHALL hold the
+/// invoice amount for the creator, both records SHALL reference each other,
+/// and the invoice SHALL become `Executed` when the escrow is released or
+/// `Expired` when it is refunded.
+///
+/// This test runs 100 iterations alternating release and refund.
+#[test]
+fn property_pay_on_delivery_settlement() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+        let amount: i128 = 3_000 + (iteration as i128 * 99);
+        let due_date = env.ledger().timestamp() + 10_000;
+        let description = SorobanString::from_str(&env, "Hardware order");
+
+        let invoice_id = client
+            .create_invoice(&creator, &client_addr, &amount, &description, &due_date, &None)
+            .invoice_id;
+        client.mark_invoice_sent(&invoice_id, &creator);
+
+        let mut conditions: Vec<Condition> = Vec::new(&env);
+        conditions.push_back(Condition {
+            condition_type: ConditionType::ManualApproval,
+            parameters: SorobanString::from_str(&env, "{\"delivery\":\"confirmed\"}"),
+            validator: client_addr.clone(),
+        });
+        let expires_at = env.ledger().timestamp() + 500 + iteration;
+
+        // Only approved invoices can be escrowed
+        assert_eq!(
+            client.try_fund_invoice_escrow(&invoice_id, &client_addr, &conditions, &expires_at),
+            Err(Ok(ContractError::InvalidInvoiceStatus))
+        );
+        client.approve_invoice(&invoice_id, &client_addr, &1);
+
+        let escrow_id = client
+            .fund_invoice_escrow(&invoice_id, &client_addr, &conditions, &expires_at)
+            .escrow_id;
+
+        // Both records reference each other
+        let escrow = client.get_escrow_details(&escrow_id);
+        let invoice = client.get_invoice(&invoice_id);
+        assert_eq!(escrow.invoice_id, Some(invoice_id));
+        assert_eq!(invoice.escrow_id, Some(escrow_id));
+        assert_eq!(escrow.sender, client_addr);
+        assert_eq!(escrow.recipient, creator);
+        assert_eq!(escrow.amount, amount);
+        assert_eq!(invoice.status, InvoiceStatus::Escrowed);
+
+        // Escrowed invoices cannot be paid directly
+        assert!(client.try_execute_invoice(&invoice_id).is_err());
+
+        if iteration % 2 == 0 {
+            let released = client.release_escrow(&escrow_id);
+            assert_eq!(released.status, EscrowStatus::Released);
+
+            let invoice = client.get_invoice(&invoice_id);
+            assert_eq!(
+                invoice.status,
+                InvoiceStatus::Executed,
+                "Iteration {}: Releasing the escrow should execute the invoice",
+                iteration
+            );
+            assert_eq!(invoice.paid_amount, amount);
+            let settlement = client.get_transaction(&invoice.settlement_tx_id.unwrap());
+            assert_eq!(settlement.recipient, creator);
+            assert_eq!(settlement.amount, amount);
+        } else {
+            env.ledger().with_mut(|li| {
+                li.timestamp = expires_at + 1;
+            });
+            let refunded = client.refund_escrow(&escrow_id);
+            assert_eq!(refunded.status, EscrowStatus::Refunded);
+
+            let invoice = client.get_invoice(&invoice_id);
+            assert_eq!(
+                invoice.status,
+                InvoiceStatus::Expired,
+                "Iteration {}: Refunding the escrow should close the invoice",
+                iteration
+            );
+            assert_eq!(invoice.paid_amount, 0);
+        }
+    }
+}
+
+/// Escrow funding applies pending credit notes like direct execution, and
+/// a refunded escrow gives the credit back
+#[test]
+fn test_escrow_funding_applies_pending_credits() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Hardware order");
+    let conditions: Vec<Condition> = Vec::new(&env);
+
+    // Earn a 300 credit on an earlier invoice
+    let earlier_id = client
+        .create_invoice(&creator, &client_addr, &1_000, &description, &due_date, &None)
+        .invoice_id;
+    client.approve_invoice(&earlier_id, &client_addr, &1);
+    client.execute_invoice(&earlier_id);
+    client.issue_credit_note(
+        &creator,
+        &earlier_id,
+        &300,
+        &SorobanString::from_str(&env, "Damaged item"),
+        &CreditDisposition::ApplyToNextInvoice,
+    );
+
+    // Funding locks only what is left after the credit
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &2_000, &description, &due_date, &None)
+        .invoice_id;
+    client.approve_invoice(&invoice_id, &client_addr, &1);
+    let expires_at = env.ledger().timestamp() + 500;
+    let escrow_id = client
+        .fund_invoice_escrow(&invoice_id, &client_addr, &conditions, &expires_at)
+        .escrow_id;
+    assert_eq!(client.get_escrow_details(&escrow_id).amount, 1_700);
+
+    // A refund restores the credit for the next invoice
+    env.ledger().with_mut(|li| {
+        li.timestamp = expires_at + 1;
+    });
+    client.refund_escrow(&escrow_id);
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Expired);
+
+    let next_id = client
+        .create_invoice(&creator, &client_addr, &2_000, &description, &due_date, &None)
+        .invoice_id;
+    client.approve_invoice(&next_id, &client_addr, &1);
+    let escrow_id = client
+        .fund_invoice_escrow(&next_id, &client_addr, &conditions, &(expires_at + 500))
+        .escrow_id;
+    assert_eq!(client.get_escrow_details(&escrow_id).amount, 1_700);
+    client.release_escrow(&escrow_id);
+    assert_eq!(client.get_invoice(&next_id).paid_amount, 1_700);
+
+    // A fully credited invoice cannot be escrowed and keeps its credit
+    // for direct execution
+    client.issue_credit_note(
+        &creator,
+        &next_id,
+        &1_000,
+        &SorobanString::from_str(&env, "Returned order"),
+        &CreditDisposition::ApplyToNextInvoice,
+    );
+    let covered_id = client
+        .create_invoice(&creator, &client_addr, &1_000, &description, &due_date, &None)
+        .invoice_id;
+    client.approve_invoice(&covered_id, &client_addr, &1);
+    assert_eq!(
+        client.try_fund_invoice_escrow(&covered_id, &client_addr, &conditions, &(expires_at + 500)),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    client.execute_invoice(&covered_id);
+    let covered = client.get_invoice(&covered_id);
+    assert_eq!(covered.status, InvoiceStatus::Executed);
+    assert_eq!(covered.paid_amount, 0);
+}
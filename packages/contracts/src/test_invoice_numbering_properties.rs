@@ -0,0 +1,180 @@
+//! Property-based tests for per-creator invoice numbering
+//! Feature: stellar-smart-contract-dapp, Property 24: Creator Numbering and References
+//! **Validates: Requirements 4.1**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, InvoiceSpec,
+};
+use soroban_sdk::{
+    testutils::Address as _, Address, BytesN, Env, Map, String as SorobanString, Vec,
+};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 24: Creator Numbering and References
+///
+/// For any interleaving of invoices from several creators, each creator's
+/// invoices SHALL be numbered 1, 2, 3, ... independently of the global ID,
+/// and every invoice SHALL be retrievable by its creator number and by its
+/// external reference.
+///
+/// This test runs 100 iterations with two or three creators issuing up to
+/// eight invoices.
+#[test]
+fn property_creator_numbering_and_references() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator_count = 2 + (iteration % 2) as u32;
+        let mut creators: Vec<Address> = Vec::new(&env);
+        for _ in 0..creator_count {
+            creators.push_back(Address::generate(&env));
+        }
+        let prefix = SorobanString::from_str(&env, "ACME-");
+        client.set_invoice_number_prefix(&creators.get(0).unwrap(), &Some(prefix.clone()));
+
+        let client_addr = Address::generate(&env);
+        let due_date = env.ledger().timestamp() + 10_000;
+        let description = SorobanString::from_str(&env, "Bookkeeping");
+        let references = ["cuid-a", "cuid-b", "cuid-c", "cuid-d", "cuid-e", "cuid-f", "cuid-g", "cuid-h"];
+        let invoice_count = 3 + (iteration % 6) as usize;
+
+        let mut next_number = [0u64; 3];
+        for (n, reference) in references.iter().take(invoice_count).enumerate() {
+            let creator_index = (n + iteration as usize) % creator_count as usize;
+            let creator = creators.get(creator_index as u32).unwrap();
+            let external_ref = SorobanString::from_str(&env, reference);
+            let invoice_id = client
+                .create_invoice(&creator, &client_addr, &100, &description, &due_date, &Some(external_ref.clone()))
+                .invoice_id;
+            next_number[creator_index] += 1;
+
+            let invoice = client.get_invoice(&invoice_id);
+            assert_eq!(
+                invoice.creator_number, next_number[creator_index],
+                "Iteration {}: Creator numbers should be sequential per creator",
+                iteration
+            );
+            assert_eq!(invoice.number_prefix.is_some(), creator_index == 0);
+            assert_eq!(invoice.external_ref, Some(external_ref.clone()));
+            assert_eq!(client.get_invoice_by_reference(&creator, &external_ref).id, invoice_id);
+            assert_eq!(client.get_invoice_by_number(&creator, &invoice.creator_number).id, invoice_id);
+
+            // The same reference cannot be reused by this creator
+            assert_eq!(
+                client.try_create_invoice(&creator, &client_addr, &100, &description, &due_date, &Some(external_ref)),
+                Err(Ok(ContractError::DuplicateReference))
+            );
+        }
+    }
+}
+
+/// References are scoped per creator and also apply to batch creation
+#[test]
+fn test_references_are_scoped_per_creator() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator_a = Address::generate(&env);
+    let creator_b = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Hosting");
+    let reference = SorobanString::from_str(&env, "ck9x2shared");
+
+    let id_a = client
+        .create_invoice(&creator_a, &client_addr, &100, &description, &due_date, &Some(reference.clone()))
+        .invoice_id;
+    let id_b = client
+        .create_invoice(&creator_b, &client_addr, &100, &description, &due_date, &Some(reference.clone()))
+        .invoice_id;
+    assert_eq!(client.get_invoice_by_reference(&creator_a, &reference).id, id_a);
+    assert_eq!(client.get_invoice_by_reference(&creator_b, &reference).id, id_b);
+    assert_eq!(client.get_invoice_by_number(&creator_b, &1).id, id_b);
+    assert_eq!(
+        client.try_get_invoice_by_number(&creator_b, &2),
+        Err(Ok(ContractError::InvoiceNotFound))
+    );
+
+    // A duplicate within a batch rejects the whole batch
+    let mut specs: Vec<InvoiceSpec> = Vec::new(&env);
+    for _ in 0..2 {
+        specs.push_back(InvoiceSpec {
+            client: client_addr.clone(),
+            amount: 100,
+            description: description.clone(),
+            due_date,
+            external_ref: Some(SorobanString::from_str(&env, "batch-ref")),
+        });
+    }
+    assert_eq!(
+        client.try_create_invoices_batch(&creator_a, &specs),
+        Err(Ok(ContractError::DuplicateReference))
+    );
+}
+
+/// Invoices cannot be created, numbered or referenced without the
+/// creator's authorization
+#[test]
+fn test_creation_requires_creator_auth() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Hosting");
+    let reference = SorobanString::from_str(&env, "inv-squat");
+    let mut specs: Vec<InvoiceSpec> = Vec::new(&env);
+    specs.push_back(InvoiceSpec {
+        client: client_addr.clone(),
+        amount: 100,
+        description: description.clone(),
+        due_date,
+        external_ref: Some(reference.clone()),
+    });
+
+    // Drop the mocked authorizations so the creator has signed nothing
+    env.set_auths(&[]);
+    assert!(client
+        .try_create_invoice(&creator, &client_addr, &100, &description, &due_date, &Some(reference.clone()))
+        .is_err());
+    assert!(client.try_create_invoices_batch(&creator, &specs).is_err());
+    let mut shares: Map<Address, i128> = Map::new(&env);
+    shares.set(client_addr.clone(), 100);
+    assert!(client
+        .try_create_split_invoice(&creator, &shares, &description, &due_date)
+        .is_err());
+    let claim_key = BytesN::from_array(&env, &[7; 32]);
+    assert!(client
+        .try_create_claimable_invoice(&creator, &claim_key, &100, &description, &due_date)
+        .is_err());
+
+    assert_eq!(
+        client.try_get_invoice_by_number(&creator, &1),
+        Err(Ok(ContractError::InvoiceNotFound))
+    );
+    assert_eq!(
+        client.try_get_invoice_by_reference(&creator, &reference),
+        Err(Ok(ContractError::InvoiceNotFound))
+    );
+
+    // Once signed, each entrypoint records the creator's authorization
+    env.mock_all_auths();
+    client.create_split_invoice(&creator, &shares, &description, &due_date);
+    assert_eq!(env.auths().first().map(|(addr, _)| addr.clone()), Some(creator.clone()));
+    client.create_claimable_invoice(&creator, &claim_key, &100, &description, &due_date);
+    assert_eq!(env.auths().first().map(|(addr, _)| addr.clone()), Some(creator.clone()));
+    assert_eq!(client.get_invoice_by_number(&creator, &1).shares.len(), 1);
+    assert_eq!(client.get_invoice_by_number(&creator, &2).claim_key, Some(claim_key));
+}
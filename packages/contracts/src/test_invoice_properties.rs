@@ -48,6 +48,7 @@ fn property_invoice_approval_workflow() {
             &amount,
             &description,
             &due_date,
+            &None,
         );
         
         assert!(create_result.is_ok(), "Invoice creation should succeed");
@@ -58,7 +59,7 @@ fn property_invoice_approval_workflow() {
         assert!(sent_result.is_ok(), "Marking invoice as sent should succeed");
         
         // Step 3: Approve invoice
-        let approve_result = client.try_approve_invoice(&invoice_id, &client_addr);
+        let approve_result = client.try_approve_invoice(&invoice_id, &client_addr, &1);
         assert!(approve_result.is_ok(), "Invoice approval should succeed");
         
         let approval_result = approve_result.unwrap().unwrap();
@@ -139,6 +140,7 @@ fn property_invoice_expiration_handling() {
             &amount,
             &description,
             &due_date,
+            &None,
         );
         
         assert!(create_result.is_ok(), "Invoice creation should succeed");
@@ -173,7 +175,7 @@ fn property_invoice_expiration_handling() {
         );
         
         // Step 6: Attempt to approve expired invoice should fail
-        let approve_result = client.try_approve_invoice(&invoice_id, &client_addr);
+        let approve_result = client.try_approve_invoice(&invoice_id, &client_addr, &1);
         assert!(
             approve_result.is_err() || approve_result.unwrap().is_err(),
             "Approving expired invoice should fail"
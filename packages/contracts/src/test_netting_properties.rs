@@ -0,0 +1,214 @@
+//! Property-based tests for net settlement of mutual invoices
+//! Feature: stellar-smart-contract-dapp, Property 26: Net Settlement Conservation
+//! **Validates: Requirements 4.3**
+
+use crate::netting::MAX_NETTING_INVOICES;
+use crate::testutils::assert_within_tx_limits;
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, CreditDisposition,
+    InvoiceStatus, TransactionLink,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Create, send and approve an invoice from `creator` to `payer`
+fn approved_invoice(
+    env: &Env,
+    client: &StellarDAppContractClient,
+    creator: &Address,
+    payer: &Address,
+    amount: i128,
+) -> u64 {
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(env, "Marketplace services");
+    let invoice_id = client
+        .create_invoice(creator, payer, &amount, &description, &due_date, &None)
+        .invoice_id;
+    client.mark_invoice_sent(&invoice_id, creator);
+    client.approve_invoice(&invoice_id, payer, &1);
+    invoice_id
+}
+
+/// Add `delta` to a party's balance
+fn adjust(balances: &mut Map<Address, i128>, party: &Address, delta: i128) {
+    balances.set(party.clone(), balances.get(party.clone()).unwrap_or(0) + delta);
+}
+
+/// Property 26: Net Settlement Conservation
+///
+/// For any set of approved invoices among a group of parties, the net
+/// transfers SHALL leave every party with the same net position as paying
+/// each invoice gross, SHALL need fewer transfers than parties, and every
+/// invoice SHALL be executed with a reference to the netting batch.
+///
+/// This test runs 100 iterations with three to five parties invoicing
+/// each other.
+#[test]
+fn property_net_settlement_conservation() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let party_count = 3 + (iteration % 3) as u32;
+        let mut parties: Vec<Address> = Vec::new(&env);
+        for _ in 0..party_count {
+            parties.push_back(Address::generate(&env));
+        }
+
+        // Every party invoices the next one, plus reverse invoices while
+        // the batch has room
+        let mut expected: Map<Address, i128> = Map::new(&env);
+        let mut invoice_ids: Vec<u64> = Vec::new(&env);
+        for n in 0..party_count {
+            let creator = parties.get(n).unwrap();
+            let payer = parties.get((n + 1) % party_count).unwrap();
+            let amount = 1_000 + ((iteration as i128 * 37 + n as i128 * 211) % 900);
+            invoice_ids.push_back(approved_invoice(&env, &client, &creator, &payer, amount));
+            adjust(&mut expected, &creator, amount);
+            adjust(&mut expected, &payer, -amount);
+
+            let remaining = party_count - n - 1;
+            if (iteration + n as u64) % 2 == 0 && invoice_ids.len() + 1 + remaining <= MAX_NETTING_INVOICES {
+                let reverse = 500 + (iteration as i128 * 13) % 700;
+                invoice_ids.push_back(approved_invoice(&env, &client, &payer, &creator, reverse));
+                adjust(&mut expected, &payer, reverse);
+                adjust(&mut expected, &creator, -reverse);
+            }
+        }
+
+        let batch = client.net_settle_invoices(&invoice_ids);
+        assert!(
+            batch.transfers.len() < party_count,
+            "Iteration {}: Netting should need fewer transfers than parties",
+            iteration
+        );
+        assert_eq!(batch.transaction_ids.len(), batch.transfers.len());
+
+        // Net transfers reproduce every party's gross position
+        let mut actual: Map<Address, i128> = Map::new(&env);
+        for (transfer, tx_id) in batch.transfers.iter().zip(batch.transaction_ids.iter()) {
+            assert!(transfer.amount > 0);
+            let transaction = client.get_transaction(&tx_id);
+            assert_eq!(transaction.amount, transfer.amount);
+            assert_eq!(transaction.link, TransactionLink::NettingBatch(batch.id));
+            adjust(&mut actual, &transfer.from, -transfer.amount);
+            adjust(&mut actual, &transfer.to, transfer.amount);
+        }
+        for party in parties.iter() {
+            assert_eq!(
+                actual.get(party.clone()).unwrap_or(0),
+                expected.get(party).unwrap_or(0),
+                "Iteration {}: Net position mismatch",
+                iteration
+            );
+        }
+
+        for invoice_id in invoice_ids.iter() {
+            let invoice = client.get_invoice(&invoice_id);
+            assert_eq!(invoice.status, InvoiceStatus::Executed);
+            assert_eq!(invoice.netting_batch, Some(batch.id));
+            assert_eq!(invoice.paid_amount, invoice.amount);
+        }
+        assert_eq!(client.get_netting_batch(&batch.id), batch);
+    }
+}
+
+/// Netting is all-or-nothing: one unapproved or repeated invoice settles nothing
+#[test]
+fn test_netting_rejects_ineligible_invoices() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let approved = approved_invoice(&env, &client, &alice, &bob, 700);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let draft = client
+        .create_invoice(&bob, &alice, &300, &SorobanString::from_str(&env, "Draft"), &due_date, &None)
+        .invoice_id;
+
+    let mut ids: Vec<u64> = Vec::new(&env);
+    ids.push_back(approved);
+    ids.push_back(draft);
+    assert_eq!(
+        client.try_net_settle_invoices(&ids),
+        Err(Ok(ContractError::InvalidInvoiceStatus))
+    );
+
+    let mut repeated: Vec<u64> = Vec::new(&env);
+    repeated.push_back(approved);
+    repeated.push_back(approved);
+    assert_eq!(
+        client.try_net_settle_invoices(&repeated),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(client.get_invoice(&approved).status, InvoiceStatus::Approved);
+}
+
+/// A full netting batch between distinct pairs fits the per-transaction
+/// resource budget even when every invoice consumes a pending credit note,
+/// and one more invoice is rejected
+#[test]
+fn test_max_netting_batch_fits_resource_budget() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let mut ids: Vec<u64> = Vec::new(&env);
+    for n in 0..=MAX_NETTING_INVOICES {
+        let creator = Address::generate(&env);
+        let payer = Address::generate(&env);
+        let credited = approved_invoice(&env, &client, &creator, &payer, 100);
+        client.execute_invoice(&credited);
+        client.issue_credit_note(
+            &creator,
+            &credited,
+            &40,
+            &SorobanString::from_str(&env, "Volume discount"),
+            &CreditDisposition::ApplyToNextInvoice,
+        );
+        ids.push_back(approved_invoice(&env, &client, &creator, &payer, 100 + n as i128));
+    }
+
+    assert_eq!(
+        client.try_net_settle_invoices(&ids),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+
+    let leftover = ids.pop_back_unchecked();
+    let batch = client.net_settle_invoices(&ids);
+    assert_within_tx_limits(&env);
+    assert_eq!(batch.invoice_ids.len(), MAX_NETTING_INVOICES);
+    assert_eq!(client.get_invoice(&ids.get_unchecked(0)).paid_amount, 60);
+    assert_eq!(client.get_invoice(&leftover).status, InvoiceStatus::Approved);
+}
+
+/// Gross and net totals that would overflow reject the batch instead of
+/// wrapping
+#[test]
+fn test_netting_overflow_is_rejected() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let amount = i128::MAX / 2;
+    let mut ids: Vec<u64> = Vec::new(&env);
+    for _ in 0..3 {
+        ids.push_back(approved_invoice(&env, &client, &alice, &bob, amount));
+    }
+
+    assert_eq!(
+        client.try_net_settle_invoices(&ids),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(client.get_invoice(&ids.get_unchecked(0)).status, InvoiceStatus::Approved);
+}
@@ -0,0 +1,240 @@
+//! Property-based tests for oracle-priced invoices
+//! Feature: stellar-smart-contract-dapp, Property 23: Oracle Conversion Bounds
+//! **Validates: Requirements 4.3**
+
+use crate::testutils::{MockPriceOracle, MockPriceOracleClient};
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ApprovalPolicy, ContractError, InvoiceStatus,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, admin, client)
+}
+
+/// Register a mock oracle with the contract
+fn setup_oracle(env: &Env, admin: &Address, client: &StellarDAppContractClient) -> MockPriceOracleClient<'static> {
+    let oracle_id = env.register(MockPriceOracle, ());
+    client.set_price_oracle(admin, &oracle_id);
+    MockPriceOracleClient::new(env, &oracle_id)
+}
+
+/// Property 23: Oracle Conversion Bounds
+///
+/// For any invoice priced in one asset and settled in another, execution
+/// SHALL charge the amount converted at the current oracle rate when that
+/// rate is fresh and within the slippage agreed at approval, and SHALL fail
+/// otherwise without executing the invoice.
+///
+/// This test runs 100 iterations moving the settlement asset price by
+/// -5% to +5% against a 2% slippage bound.
+#[test]
+fn property_oracle_conversion_bounds() {
+    for iteration in 0..100u64 {
+        let (env, admin, client) = setup_test_env();
+        let oracle = setup_oracle(&env, &admin, &client);
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+        let usd = Address::generate(&env);
+        let xlm = Address::generate(&env);
+        let now = env.ledger().timestamp();
+
+        // 1 USD = 10 XLM at approval
+        oracle.set_price(&usd, &10_000_000, &now);
+        oracle.set_price(&xlm, &1_000_000, &now);
+
+        let amount: i128 = 1_000 + (iteration as i128 * 10);
+        let due_date = now + 10_000;
+        let description = SorobanString::from_str(&env, "USD-priced retainer");
+        let invoice_id = client
+            .create_invoice(&creator, &client_addr, &amount, &description, &due_date, &None)
+            .invoice_id;
+        client.set_invoice_pricing(&invoice_id, &creator, &usd, &xlm, &300);
+        client.mark_invoice_sent(&invoice_id, &creator);
+        client.approve_invoice_with_quote(&invoice_id, &client_addr, &2, &200);
+        assert_eq!(client.get_invoice(&invoice_id).quoted_rate, 100_000_000);
+
+        // XLM moves from -5% to +5% in 0.1% steps; a cheaper XLM means more XLM per USD
+        let move_bps = (iteration as i128 % 101) * 10 - 500;
+        let xlm_price = 1_000_000 * (10_000 + move_bps) / 10_000;
+        env.ledger().with_mut(|li| {
+            li.timestamp = now + 60;
+        });
+        oracle.set_price(&usd, &10_000_000, &(now + 60));
+        oracle.set_price(&xlm, &xlm_price, &(now + 60));
+
+        let rate = 10_000_000i128 * 10_000_000 / xlm_price;
+        let within_bounds = (rate - 100_000_000) * 10_000 <= 100_000_000 * 200;
+        let result = client.try_execute_invoice(&invoice_id);
+        let invoice = client.get_invoice(&invoice_id);
+        if within_bounds {
+            assert!(result.is_ok(), "Iteration {}: Rate within bounds should execute", iteration);
+            assert_eq!(invoice.status, InvoiceStatus::Executed);
+            assert_eq!(invoice.paid_amount, amount);
+            assert_eq!(invoice.settled_amount, amount * rate / 10_000_000);
+            let settlement = client.get_transaction(&invoice.settlement_tx_id.unwrap());
+            assert_eq!(settlement.amount, invoice.settled_amount);
+        } else {
+            assert_eq!(
+                result,
+                Err(Ok(ContractError::SlippageExceeded)),
+                "Iteration {}: Adverse move beyond slippage should fail",
+                iteration
+            );
+            assert_eq!(invoice.status, InvoiceStatus::Approved);
+        }
+    }
+}
+
+/// Stale prices block both approval quotes and execution
+#[test]
+fn test_stale_prices_are_rejected() {
+    let (env, admin, client) = setup_test_env();
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let usd = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 10_000;
+    let description = SorobanString::from_str(&env, "Audit");
+
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &5_000, &description, &due_date, &None)
+        .invoice_id;
+    client.set_invoice_pricing(&invoice_id, &creator, &usd, &usdc, &300);
+
+    // No oracle registered yet, and only the admin can register one
+    assert_eq!(
+        client.try_approve_invoice_with_quote(&invoice_id, &client_addr, &2, &100),
+        Err(Ok(ContractError::OracleNotConfigured))
+    );
+    let oracle_id = env.register(MockPriceOracle, ());
+    assert_eq!(
+        client.try_set_price_oracle(&creator, &oracle_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    let oracle = setup_oracle(&env, &admin, &client);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_000;
+    });
+    oracle.set_price(&usd, &10_000_000, &1_000);
+    oracle.set_price(&usdc, &10_000_000, &500);
+    assert_eq!(
+        client.try_approve_invoice_with_quote(&invoice_id, &client_addr, &2, &100),
+        Err(Ok(ContractError::PriceUnavailable))
+    );
+
+    oracle.set_price(&usdc, &10_000_000, &1_000);
+    client.approve_invoice_with_quote(&invoice_id, &client_addr, &2, &100);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_400;
+    });
+    assert_eq!(
+        client.try_execute_invoice(&invoice_id),
+        Err(Ok(ContractError::PriceUnavailable))
+    );
+}
+
+/// Priced invoices are only approved against a quote taken at the final
+/// approval, and amending them drops the quote
+#[test]
+fn test_quote_follows_final_approval() {
+    let (env, admin, client) = setup_test_env();
+    let oracle = setup_oracle(&env, &admin, &client);
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let manager = Address::generate(&env);
+    let finance = Address::generate(&env);
+    let usd = Address::generate(&env);
+    let xlm = Address::generate(&env);
+    let now = env.ledger().timestamp();
+    let due_date = now + 10_000;
+    let description = SorobanString::from_str(&env, "Consulting");
+
+    client.set_approval_policy(
+        &client_addr,
+        &ApprovalPolicy {
+            approvers: Vec::from_array(&env, [manager.clone(), finance.clone()]),
+            threshold: 2,
+            ordered: false,
+            min_amount: 0,
+        },
+    );
+    oracle.set_price(&usd, &10_000_000, &now);
+    oracle.set_price(&xlm, &1_000_000, &now);
+
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &1_000, &description, &due_date, &None)
+        .invoice_id;
+    client.set_invoice_pricing(&invoice_id, &creator, &usd, &xlm, &300);
+    client.mark_invoice_sent(&invoice_id, &creator);
+
+    // Pricing changes the terms, and priced invoices need a quote
+    assert_eq!(
+        client.try_approve_invoice_with_quote(&invoice_id, &manager, &1, &100),
+        Err(Ok(ContractError::InvoiceVersionMismatch))
+    );
+    assert_eq!(
+        client.try_approve_invoice(&invoice_id, &manager, &2),
+        Err(Ok(ContractError::PriceUnavailable))
+    );
+
+    // A partial approval does not fix the quote
+    client.approve_invoice_with_quote(&invoice_id, &manager, &2, &100);
+    assert_eq!(client.get_invoice(&invoice_id).quoted_rate, 0);
+
+    oracle.set_price(&xlm, &2_000_000, &now);
+    client.approve_invoice_with_quote(&invoice_id, &finance, &2, &500);
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Approved);
+    assert_eq!(invoice.quoted_rate, 50_000_000);
+    assert_eq!(invoice.max_slippage_bps, 500);
+
+    client.amend_invoice(&invoice_id, &creator, &1_200, &description, &due_date);
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Sent);
+    assert_eq!(invoice.quoted_rate, 0);
+    assert_eq!(invoice.max_slippage_bps, 0);
+}
+
+/// Slippage checks on extreme rates fail cleanly instead of overflowing
+#[test]
+fn test_extreme_rate_slippage_does_not_overflow() {
+    let (env, admin, client) = setup_test_env();
+    let oracle = setup_oracle(&env, &admin, &client);
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let usd = Address::generate(&env);
+    let xlm = Address::generate(&env);
+    let now = env.ledger().timestamp();
+    let due_date = now + 10_000;
+    let description = SorobanString::from_str(&env, "Consulting");
+
+    oracle.set_price(&usd, &(i128::MAX / 100_000_000), &now);
+    oracle.set_price(&xlm, &1, &now);
+
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &1_000, &description, &due_date, &None)
+        .invoice_id;
+    client.set_invoice_pricing(&invoice_id, &creator, &usd, &xlm, &300);
+    client.mark_invoice_sent(&invoice_id, &creator);
+    client.approve_invoice_with_quote(&invoice_id, &client_addr, &2, &10_000);
+
+    assert_eq!(
+        client.try_execute_invoice(&invoice_id),
+        Err(Ok(ContractError::SlippageExceeded))
+    );
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Approved);
+}
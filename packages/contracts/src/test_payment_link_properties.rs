@@ -0,0 +1,160 @@
+//! Property-based tests for claimable payment links
+//! Feature: stellar-smart-contract-dapp, Property 33: Key-Bound Payment Links
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, PaymentLinkStatus,
+    TransactionLink,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger}, xdr::ToXdr, Address, BytesN, Env, String as SorobanString,
+};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Derive a claim keypair from a seed, returning it with its public key
+fn claim_keypair(env: &Env, seed: [u8; 32]) -> (SigningKey, BytesN<32>) {
+    let key = SigningKey::from_bytes(&seed);
+    let public_key = BytesN::from_array(env, &key.verifying_key().to_bytes());
+    (key, public_key)
+}
+
+/// Sign a claim of `link_id` to `recipient` the way the contract verifies it
+fn sign_claim(
+    env: &Env,
+    key: &SigningKey,
+    contract_id: &Address,
+    link_id: u64,
+    recipient: &Address,
+) -> BytesN<64> {
+    let message = (contract_id.clone(), link_id, recipient.clone()).to_xdr(env);
+    let mut buf = [0u8; 256];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut buf[..len]);
+    BytesN::from_array(env, &key.sign(&buf[..len]).to_bytes())
+}
+
+/// Property 33: Key-Bound Payment Links
+///
+/// For any payment link, only a signature by its claim key SHALL claim it,
+/// the claim SHALL pay the full amount to the address that was signed and
+/// to no other, and once claimed the sender SHALL no longer be able to
+/// reclaim it.
+///
+/// This test runs 100 iterations with varied claim keys and amounts.
+#[test]
+fn property_key_bound_payment_links() {
+    for iteration in 0..100u64 {
+        let (env, contract_id, client) = setup_test_env();
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let front_runner = Address::generate(&env);
+
+        let mut seed = [0u8; 32];
+        seed[..8].copy_from_slice(&iteration.to_be_bytes());
+        seed[31] = 0x5A;
+        let (key, claim_key) = claim_keypair(&env, seed);
+
+        let amount: i128 = 300 + (iteration as i128 * 55);
+        let expires_at = env.ledger().timestamp() + 86_400;
+        let memo = SorobanString::from_str(&env, "Welcome gift");
+        let link_id = client.create_payment_link(&sender, &claim_key, &amount, &memo, &expires_at);
+
+        // A wrong key fails signature verification in the host
+        let mut wrong_seed = seed;
+        wrong_seed[31] ^= 0xFF;
+        let (wrong_key, _) = claim_keypair(&env, wrong_seed);
+        let forged = sign_claim(&env, &wrong_key, &contract_id, link_id, &recipient);
+        assert!(
+            matches!(client.try_claim_payment_link(&link_id, &recipient, &forged), Err(Err(_))),
+            "Iteration {}: Wrong key should not claim the link",
+            iteration
+        );
+
+        // A claim seen in flight cannot be redirected
+        let signature = sign_claim(&env, &key, &contract_id, link_id, &recipient);
+        assert!(
+            matches!(client.try_claim_payment_link(&link_id, &front_runner, &signature), Err(Err(_))),
+            "Iteration {}: Signature should only pay the signed recipient",
+            iteration
+        );
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = expires_at - iteration % 2;
+        });
+        let result = client.claim_payment_link(&link_id, &recipient, &signature);
+        let transaction = client.get_transaction(&result.transaction_id);
+        assert_eq!(transaction.sender, sender);
+        assert_eq!(transaction.recipient, recipient);
+        assert_eq!(transaction.amount, amount);
+        assert_eq!(transaction.link, TransactionLink::PaymentLink(link_id));
+
+        let link = client.get_payment_link(&link_id);
+        assert_eq!(link.status, PaymentLinkStatus::Claimed);
+        assert_eq!(link.claimed_by, Some(recipient.clone()));
+        assert_eq!(link.transaction_id, Some(result.transaction_id));
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = expires_at + 1;
+        });
+        assert_eq!(
+            client.try_reclaim_payment_link(&link_id, &sender),
+            Err(Ok(ContractError::PaymentLinkClosed))
+        );
+        assert_eq!(
+            client.try_claim_payment_link(&link_id, &sender, &sign_claim(&env, &key, &contract_id, link_id, &sender)),
+            Err(Ok(ContractError::PaymentLinkClosed))
+        );
+    }
+}
+
+/// Unclaimed links can be reclaimed by the sender only after expiry
+#[test]
+fn test_expired_link_is_reclaimed() {
+    let (env, contract_id, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (key, claim_key) = claim_keypair(&env, [7u8; 32]);
+    let memo = SorobanString::from_str(&env, "Invite bonus");
+
+    let link_id = client.create_payment_link(&sender, &claim_key, &1_000, &memo, &500);
+    assert_eq!(
+        client.try_reclaim_payment_link(&link_id, &sender),
+        Err(Ok(ContractError::PaymentLinkNotExpired))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 501;
+    });
+    assert_eq!(
+        client.try_claim_payment_link(&link_id, &recipient, &sign_claim(&env, &key, &contract_id, link_id, &recipient)),
+        Err(Ok(ContractError::PaymentLinkExpired))
+    );
+    assert_eq!(
+        client.try_reclaim_payment_link(&link_id, &recipient),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    let link = client.reclaim_payment_link(&link_id, &sender);
+    assert_eq!(link.status, PaymentLinkStatus::Reclaimed);
+    assert_eq!(link.transaction_id, None);
+    assert_eq!(
+        client.try_reclaim_payment_link(&link_id, &sender),
+        Err(Ok(ContractError::PaymentLinkClosed))
+    );
+}
@@ -0,0 +1,85 @@
+//! Property-based tests for P2P payment requests
+//! Feature: stellar-smart-contract-dapp, Property 25: Payment Request Settlement
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, PaymentRequestStatus,
+    TransactionLink, TransactionType,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String as SorobanString};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 25: Payment Request Settlement
+///
+/// For any payment request, only the addressed payer SHALL be able to pay
+/// or decline it, it SHALL be settled at most once, and paying it SHALL
+/// record a P2P transaction for the requested amount linked to the request.
+///
+/// This test runs 100 iterations alternating pay and decline.
+#[test]
+fn property_payment_request_settlement() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let payee = Address::generate(&env);
+        let payer = Address::generate(&env);
+        let amount: i128 = 50 + (iteration as i128 * 17);
+        let memo = SorobanString::from_str(&env, "Dinner split");
+
+        let request_id = client.create_payment_request(&payee, &payer, &amount, &memo);
+        assert_eq!(client.get_payment_request(&request_id).status, PaymentRequestStatus::Pending);
+
+        // Someone else cannot settle the request
+        let stranger = Address::generate(&env);
+        assert_eq!(
+            client.try_pay_payment_request(&request_id, &stranger),
+            Err(Ok(ContractError::Unauthorized))
+        );
+
+        if iteration % 2 == 0 {
+            let result = client.pay_payment_request(&request_id, &payer);
+            let transaction = client.get_transaction(&result.transaction_id);
+            assert_eq!(transaction.transaction_type, TransactionType::P2P);
+            assert_eq!(transaction.sender, payer);
+            assert_eq!(transaction.recipient, payee);
+            assert_eq!(transaction.amount, amount);
+            assert_eq!(transaction.metadata, memo);
+            assert_eq!(
+                transaction.link,
+                TransactionLink::PaymentRequest(request_id),
+                "Iteration {}: Payment should link back to the request",
+                iteration
+            );
+
+            let request = client.get_payment_request(&request_id);
+            assert_eq!(request.status, PaymentRequestStatus::Paid);
+            assert_eq!(request.transaction_id, Some(result.transaction_id));
+        } else {
+            client.decline_payment_request(&request_id, &payer);
+            assert_eq!(client.get_payment_request(&request_id).status, PaymentRequestStatus::Declined);
+        }
+
+        // Settled requests cannot be paid or declined again
+        assert_eq!(
+            client.try_pay_payment_request(&request_id, &payer),
+            Err(Ok(ContractError::PaymentRequestClosed))
+        );
+        assert_eq!(
+            client.try_decline_payment_request(&request_id, &payer),
+            Err(Ok(ContractError::PaymentRequestClosed))
+        );
+    }
+}
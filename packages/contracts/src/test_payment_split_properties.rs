@@ -0,0 +1,144 @@
+//! Property-based tests for percentage-based payment splits
+//! Feature: stellar-smart-contract-dapp, Property 28: Exact Split Allocation
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, SplitShare, TransactionLink,
+    TransactionType,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 28: Exact Split Allocation
+///
+/// For any split config and payment amount, the recorded legs SHALL sum
+/// exactly to the amount, every leg other than the first SHALL equal its
+/// basis-point share rounded down, and the first leg SHALL absorb the
+/// rounding remainder.
+///
+/// This test runs 100 iterations with two to four recipients and amounts
+/// chosen to produce rounding remainders.
+#[test]
+fn property_exact_split_allocation() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let owner = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient_count = 2 + (iteration % 3) as u32;
+
+        // Uneven shares: the last recipient takes whatever is left of 10,000
+        let mut shares: Vec<SplitShare> = Vec::new(&env);
+        let mut assigned = 0u32;
+        for n in 0..recipient_count {
+            let bps = if n + 1 == recipient_count {
+                10_000 - assigned
+            } else {
+                1_000 + ((iteration as u32 * 7 + n * 1_333) % 2_000)
+            };
+            assigned += bps;
+            shares.push_back(SplitShare { recipient: Address::generate(&env), bps, handle: None, handle_owner: None });
+        }
+        let split_id = client.create_split_config(&owner, &shares);
+
+        let amount: i128 = 997 + (iteration as i128 * 7_919);
+        let memo = SorobanString::from_str(&env, "Collab sale");
+        let transaction_ids = client.execute_p2p_split_transaction(&sender, &split_id, &amount, &memo);
+        assert_eq!(transaction_ids.len(), recipient_count);
+
+        let mut total: i128 = 0;
+        for (n, (share, tx_id)) in shares.iter().zip(transaction_ids.iter()).enumerate() {
+            let transaction = client.get_transaction(&tx_id);
+            assert_eq!(transaction.transaction_type, TransactionType::P2P);
+            assert_eq!(transaction.sender, sender);
+            assert_eq!(transaction.recipient, share.recipient);
+            assert_eq!(transaction.link, TransactionLink::SplitConfig(split_id));
+            let floor = amount * share.bps as i128 / 10_000;
+            if n == 0 {
+                assert!(transaction.amount >= floor);
+            } else {
+                assert_eq!(transaction.amount, floor);
+            }
+            total += transaction.amount;
+        }
+        assert_eq!(
+            total, amount,
+            "Iteration {}: Split legs should sum exactly to the amount",
+            iteration
+        );
+    }
+}
+
+/// Split configs must cover exactly 100% with distinct recipients
+#[test]
+fn test_invalid_split_configs_are_rejected() {
+    let (env, _contract_id, client) = setup_test_env();
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let mut short: Vec<SplitShare> = Vec::new(&env);
+    short.push_back(SplitShare { recipient: alice.clone(), bps: 6_000, handle: None, handle_owner: None });
+    short.push_back(SplitShare { recipient: bob.clone(), bps: 3_999, handle: None, handle_owner: None });
+    assert_eq!(
+        client.try_create_split_config(&owner, &short),
+        Err(Ok(ContractError::InvalidSplitConfig))
+    );
+
+    let mut duplicated: Vec<SplitShare> = Vec::new(&env);
+    duplicated.push_back(SplitShare { recipient: alice.clone(), bps: 5_000, handle: None, handle_owner: None });
+    duplicated.push_back(SplitShare { recipient: alice, bps: 5_000, handle: None, handle_owner: None });
+    assert_eq!(
+        client.try_create_split_config(&owner, &duplicated),
+        Err(Ok(ContractError::InvalidSplitConfig))
+    );
+
+    assert_eq!(
+        client.try_execute_p2p_split_transaction(&owner, &99, &100, &SorobanString::from_str(&env, "x")),
+        Err(Ok(ContractError::SplitConfigNotFound))
+    );
+}
+
+/// Amounts up to the largest valid amount split exactly without overflow
+#[test]
+fn test_split_near_amount_limit() {
+    let (env, _contract_id, client) = setup_test_env();
+    let owner = Address::generate(&env);
+    let sender = Address::generate(&env);
+
+    let mut shares: Vec<SplitShare> = Vec::new(&env);
+    for bps in [3_333u32, 3_333, 3_334] {
+        shares.push_back(SplitShare { recipient: Address::generate(&env), bps, handle: None, handle_owner: None });
+    }
+    let split_id = client.create_split_config(&owner, &shares);
+
+    let amount = i128::MAX / 2;
+    let memo = SorobanString::from_str(&env, "Treasury split");
+    let transaction_ids = client.execute_p2p_split_transaction(&sender, &split_id, &amount, &memo);
+
+    let mut total: i128 = 0;
+    for (n, (share, tx_id)) in shares.iter().zip(transaction_ids.iter()).enumerate() {
+        let leg = client.get_transaction(&tx_id).amount;
+        let floor = (amount / 10_000) * share.bps as i128 + (amount % 10_000) * share.bps as i128 / 10_000;
+        if n == 0 {
+            assert!(leg >= floor);
+        } else {
+            assert_eq!(leg, floor);
+        }
+        total += leg;
+    }
+    assert_eq!(total, amount);
+}
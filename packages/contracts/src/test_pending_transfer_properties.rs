@@ -0,0 +1,129 @@
+//! Property-based tests for two-phase pending P2P transfers
+//! Feature: stellar-smart-contract-dapp, Property 32: Pending Transfer Resolution
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, TransactionStatus,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 32: Pending Transfer Resolution
+///
+/// For any pending transfer, exactly one of claim, cancel or expiry SHALL
+/// resolve it: a claim before the deadline confirms it, a cancellation or
+/// an expiry after the deadline cancels it, and every other action on the
+/// resolved transfer SHALL fail.
+///
+/// This test runs 100 iterations rotating between the three resolutions
+/// at varying times relative to the deadline.
+#[test]
+fn property_pending_transfer_resolution() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let amount: i128 = 250 + (iteration as i128 * 41);
+        let memo = SorobanString::from_str(&env, "Dinner");
+        let expires_at = 1_000 + iteration * 10;
+
+        let result = client.send_pending_p2p_transfer(&sender, &recipient, &amount, &memo, &expires_at);
+        assert_eq!(result.status, TransactionStatus::Pending);
+        assert_eq!(client.get_transaction(&result.transaction_id).status, TransactionStatus::Pending);
+        assert_eq!(client.get_pending_transfer_expiry(&result.transaction_id), expires_at);
+
+        let now = expires_at - 5 + iteration % 11;
+        env.ledger().with_mut(|li| {
+            li.timestamp = now;
+        });
+        let expired = now > expires_at;
+
+        let expected = match iteration % 3 {
+            0 if !expired => {
+                client.claim_pending_transfer(&result.transaction_id, &recipient);
+                TransactionStatus::Confirmed
+            }
+            0 => {
+                assert_eq!(
+                    client.try_claim_pending_transfer(&result.transaction_id, &recipient),
+                    Err(Ok(ContractError::TransferExpired)),
+                    "Iteration {}: Expired transfers cannot be claimed",
+                    iteration
+                );
+                client.expire_pending_transfer(&result.transaction_id);
+                TransactionStatus::Cancelled
+            }
+            1 => {
+                client.cancel_pending_transfer(&result.transaction_id, &sender);
+                TransactionStatus::Cancelled
+            }
+            _ if expired => {
+                client.expire_pending_transfer(&result.transaction_id);
+                TransactionStatus::Cancelled
+            }
+            _ => {
+                assert_eq!(
+                    client.try_expire_pending_transfer(&result.transaction_id),
+                    Err(Ok(ContractError::TransferNotExpired))
+                );
+                client.claim_pending_transfer(&result.transaction_id, &recipient);
+                TransactionStatus::Confirmed
+            }
+        };
+
+        let transaction = client.get_transaction(&result.transaction_id);
+        assert_eq!(transaction.status, expected, "Iteration {}: Unexpected resolution", iteration);
+        assert_eq!(transaction.amount, amount);
+
+        // A resolved transfer cannot be resolved again
+        let not_pending = Err(Ok(ContractError::TransferNotPending));
+        assert_eq!(client.try_claim_pending_transfer(&result.transaction_id, &recipient), not_pending);
+        assert_eq!(client.try_cancel_pending_transfer(&result.transaction_id, &sender), not_pending);
+        assert_eq!(client.try_expire_pending_transfer(&result.transaction_id), not_pending);
+    }
+}
+
+/// Only the parties to a pending transfer can claim or cancel it
+#[test]
+fn test_pending_transfer_parties() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let memo = SorobanString::from_str(&env, "Deposit");
+
+    let result = client.send_pending_p2p_transfer(&sender, &recipient, &100, &memo, &500);
+    assert_eq!(
+        client.try_claim_pending_transfer(&result.transaction_id, &sender),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_cancel_pending_transfer(&result.transaction_id, &recipient),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // Immediate P2P transfers are never pending
+    let direct = client.execute_p2p_transaction(&sender, &recipient, &100, &memo);
+    assert_eq!(
+        client.try_cancel_pending_transfer(&direct.transaction_id, &sender),
+        Err(Ok(ContractError::TransferNotPending))
+    );
+    assert_eq!(
+        client.try_send_pending_p2p_transfer(&sender, &recipient, &100, &memo, &0),
+        Err(Ok(ContractError::InvalidSchedule))
+    );
+}
@@ -0,0 +1,190 @@
+//! Property-based tests for transferable invoice receivables
+//! Feature: stellar-smart-contract-dapp, Property 18: Receivable Holder Settlement
+//! **Validates: Requirements 4.3**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, CreditDisposition,
+    CreditNoteStatus, InvoiceStatus, TransactionType,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String as SorobanString};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 18: Receivable Holder Settlement
+///
+/// For any chain of receivable transfers on an outstanding invoice, the
+/// transfer chain SHALL be recorded in order, only the current holder SHALL
+/// be able to transfer it, and execution SHALL pay the final holder.
+///
+/// This test runs 100 iterations with chains of one to four transfers.
+#[test]
+fn property_receivable_holder_settlement() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+        let amount: i128 = 10_000 + (iteration as i128 * 500);
+        let due_date = env.ledger().timestamp() + 30 * 86_400;
+        let description = SorobanString::from_str(&env, "Net-30 receivable");
+
+        let invoice_id = client
+            .create_invoice(&creator, &client_addr, &amount, &description, &due_date, &None)
+            .invoice_id;
+
+        // Draft invoices are not yet receivables
+        let factor = Address::generate(&env);
+        assert_eq!(
+            client.try_transfer_receivable(&invoice_id, &creator, &factor),
+            Err(Ok(ContractError::InvalidInvoiceStatus))
+        );
+        client.mark_invoice_sent(&invoice_id, &creator);
+
+        let transfers = 1 + (iteration % 4) as u32;
+        let mut holder = creator.clone();
+        for n in 0..transfers {
+            // Approve midway so transfers of approved receivables are covered too
+            if n == 1 {
+                client.approve_invoice(&invoice_id, &client_addr, &1);
+            }
+            let next = Address::generate(&env);
+            client.transfer_receivable(&invoice_id, &holder, &next);
+
+            // The previous holder can no longer transfer it
+            assert_eq!(
+                client.try_transfer_receivable(&invoice_id, &holder, &creator),
+                Err(Ok(ContractError::Unauthorized)),
+                "Iteration {}: Former holder should not be able to transfer",
+                iteration
+            );
+            holder = next;
+        }
+        if transfers == 1 {
+            client.approve_invoice(&invoice_id, &client_addr, &1);
+        }
+
+        let chain = client.get_receivable_transfers(&invoice_id);
+        assert_eq!(chain.len(), transfers);
+        assert_eq!(chain.get(0).unwrap().from, creator);
+        assert_eq!(chain.last().unwrap().to, holder);
+        for i in 1..chain.len() {
+            assert_eq!(chain.get(i).unwrap().from, chain.get(i - 1).unwrap().to);
+        }
+
+        // Execution pays the current holder rather than the creator
+        client.execute_invoice(&invoice_id);
+        let invoice = client.get_invoice(&invoice_id);
+        assert_eq!(invoice.status, InvoiceStatus::Executed);
+        assert_eq!(invoice.receivable_holder, holder);
+        let settlement = client.get_transaction(&invoice.settlement_tx_id.unwrap());
+        assert_eq!(settlement.transaction_type, TransactionType::Invoice);
+        assert_eq!(settlement.sender, client_addr);
+        assert_eq!(
+            settlement.recipient, holder,
+            "Iteration {}: Payment should go to the final holder",
+            iteration
+        );
+        assert_eq!(settlement.amount, amount);
+
+        // Settled receivables can no longer be transferred
+        assert_eq!(
+            client.try_transfer_receivable(&invoice_id, &holder, &creator),
+            Err(Ok(ContractError::InvalidInvoiceStatus))
+        );
+    }
+}
+
+/// Once the receivable is sold, the creator can no longer amend or cancel
+/// the invoice, and the sale appears in the status history
+#[test]
+fn test_creator_cannot_undercut_sold_receivable() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let factor = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 30 * 86_400;
+    let description = SorobanString::from_str(&env, "Factored receivable");
+
+    let invoice_id = client
+        .create_invoice(&creator, &client_addr, &5_000, &description, &due_date, &None)
+        .invoice_id;
+    client.mark_invoice_sent(&invoice_id, &creator);
+    client.transfer_receivable(&invoice_id, &creator, &factor);
+
+    let history = client.get_invoice_history(&invoice_id);
+    let entry = history.last().unwrap();
+    assert_eq!(entry.actor, Some(creator.clone()));
+    assert_eq!(entry.note, Some(SorobanString::from_str(&env, "receivable transferred")));
+
+    assert_eq!(
+        client.try_amend_invoice(&invoice_id, &creator, &1, &description, &due_date),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_cancel_invoice(&invoice_id, &creator),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.amount, 5_000);
+    assert_eq!(invoice.status, InvoiceStatus::Sent);
+}
+
+/// The creator's pending credits are not taken out of a sold receivable;
+/// the holder is paid in full and the credit waits for the next invoice
+#[test]
+fn test_sold_receivable_is_not_credited() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let factor = Address::generate(&env);
+    let due_date = env.ledger().timestamp() + 30 * 86_400;
+    let description = SorobanString::from_str(&env, "Factored receivable");
+
+    let invoice = |amount: i128| {
+        let invoice_id = client
+            .create_invoice(&creator, &client_addr, &amount, &description, &due_date, &None)
+            .invoice_id;
+        client.mark_invoice_sent(&invoice_id, &creator);
+        invoice_id
+    };
+
+    let credited = invoice(5_000);
+    client.approve_invoice(&credited, &client_addr, &1);
+    client.execute_invoice(&credited);
+    let note = client.issue_credit_note(
+        &creator,
+        &credited,
+        &1_000,
+        &SorobanString::from_str(&env, "Damaged goods"),
+        &CreditDisposition::ApplyToNextInvoice,
+    );
+
+    let sold = invoice(5_000);
+    client.transfer_receivable(&sold, &creator, &factor);
+    client.approve_invoice(&sold, &client_addr, &1);
+    client.execute_invoice(&sold);
+    let settlement = client.get_transaction(&client.get_invoice(&sold).settlement_tx_id.unwrap());
+    assert_eq!(settlement.recipient, factor);
+    assert_eq!(settlement.amount, 5_000);
+    assert_eq!(client.get_credit_note(&note.credit_note_id).status, CreditNoteStatus::Pending);
+
+    let next = invoice(5_000);
+    client.approve_invoice(&next, &client_addr, &1);
+    client.execute_invoice(&next);
+    assert_eq!(client.get_invoice(&next).paid_amount, 4_000);
+    assert_eq!(client.get_credit_note(&note.credit_note_id).status, CreditNoteStatus::Applied);
+}
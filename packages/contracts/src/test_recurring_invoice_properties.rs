@@ -0,0 +1,269 @@
+//! Property-based tests for recurring invoices
+//! Feature: stellar-smart-contract-dapp, Property 12: Recurring Invoice Billing
+//! **Validates: Requirements 4.1, 4.3**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ApprovalPolicy, ContractError, InvoiceStatus,
+    RecurringInvoiceStatus, RecurringSchedule,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 12: Recurring Invoice Billing
+///
+/// For any bounded subscription that the client has authorized for
+/// pull-payment, each billing period SHALL materialize exactly one approved
+/// invoice for the template amount, payable without further client
+/// approval, and no invoice SHALL be issued before its period starts or
+/// after the last cycle.
+///
+/// This test runs 100 iterations with varied amounts, periods and cycle
+/// counts.
+#[test]
+fn property_recurring_invoice_billing() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let client_addr = Address::generate(&env);
+
+        let amount: i128 = 500 + (iteration as i128 * 1_000);
+        let period = 3_600 + (iteration * 60);
+        let max_cycles = 1 + (iteration % 4) as u32;
+        let start = env.ledger().timestamp() + 100;
+        let description = SorobanString::from_str(&env, "Monthly subscription");
+
+        // --- Step 1: Create and authorize the subscription ---
+        let recurring_id = client
+            .create_recurring_invoice(
+                &creator,
+                &client_addr,
+                &amount,
+                &description,
+                &RecurringSchedule {
+                    period,
+                    start,
+                    end_date: None,
+                    max_cycles: Some(max_cycles),
+                },
+            )
+            .recurring_id;
+        client.authorize_recurring_payment(&recurring_id, &client_addr);
+
+        // --- Step 2: Nothing can be issued before the first period starts ---
+        let early = client.try_generate_recurring_invoice(&recurring_id);
+        assert_eq!(
+            early,
+            Err(Ok(ContractError::CycleNotDue)),
+            "Iteration {}: No invoice should be issued before the start",
+            iteration
+        );
+
+        // --- Step 3: Issue and pay every cycle ---
+        for cycle in 0..max_cycles as u64 {
+            let period_start = start + cycle * period;
+            env.ledger().with_mut(|li| {
+                li.timestamp = period_start;
+            });
+
+            let issued = client.generate_recurring_invoice(&recurring_id);
+            assert_eq!(
+                issued.status,
+                InvoiceStatus::Approved,
+                "Iteration {}: Pull-authorized cycles should be pre-approved",
+                iteration
+            );
+
+            let invoice = client.get_invoice(&issued.invoice_id);
+            assert_eq!(invoice.amount, amount);
+            assert_eq!(invoice.client, Some(client_addr.clone()));
+            assert_eq!(invoice.due_date, period_start + period);
+            assert_eq!(invoice.recurring_id, Some(recurring_id));
+
+            // A second invoice for the same period must not be issued
+            if cycle + 1 < max_cycles as u64 {
+                assert_eq!(
+                    client.try_generate_recurring_invoice(&recurring_id),
+                    Err(Ok(ContractError::CycleNotDue)),
+                    "Iteration {}: Cycle {} should only be issued once",
+                    iteration, cycle
+                );
+            }
+
+            let executed = client.execute_invoice(&issued.invoice_id);
+            assert_eq!(executed.status, InvoiceStatus::Executed);
+        }
+
+        // --- Step 4: The template completes after the last cycle ---
+        let recurring = client.get_recurring_invoice(&recurring_id);
+        assert_eq!(recurring.cycles_issued, max_cycles);
+        assert_eq!(
+            recurring.status,
+            RecurringInvoiceStatus::Completed,
+            "Iteration {}: Template should complete after the last cycle",
+            iteration
+        );
+        assert_eq!(
+            client.try_generate_recurring_invoice(&recurring_id),
+            Err(Ok(ContractError::RecurringInvoiceInactive))
+        );
+    }
+}
+
+/// A cancelled subscription issues no further invoices and revokes the
+/// client's pull-payment authorization.
+#[test]
+fn test_recurring_invoice_cancellation() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let start = env.ledger().timestamp();
+    let end_date = start + 10 * 86_400;
+    let description = SorobanString::from_str(&env, "Daily plan");
+
+    let recurring_id = client
+        .create_recurring_invoice(
+            &creator,
+            &client_addr,
+            &1_000i128,
+            &description,
+            &RecurringSchedule {
+                period: 86_400,
+                start,
+                end_date: Some(end_date),
+                max_cycles: None,
+            },
+        )
+        .recurring_id;
+
+    // Without pull authorization the cycle is only sent to the client
+    let first = client.generate_recurring_invoice(&recurring_id);
+    assert_eq!(first.status, InvoiceStatus::Sent);
+
+    client.authorize_recurring_payment(&recurring_id, &client_addr);
+    assert!(client.get_recurring_invoice(&recurring_id).pull_authorized);
+
+    // Only the client or creator may cancel
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_recurring_invoice(&recurring_id, &stranger),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    let cancelled = client.cancel_recurring_invoice(&recurring_id, &client_addr);
+    assert_eq!(cancelled.status, RecurringInvoiceStatus::Cancelled);
+    assert!(!client.get_recurring_invoice(&recurring_id).pull_authorized);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = start + 86_400;
+    });
+    assert_eq!(
+        client.try_generate_recurring_invoice(&recurring_id),
+        Err(Ok(ContractError::RecurringInvoiceInactive))
+    );
+}
+
+/// Unbounded or zero-period schedules are rejected.
+#[test]
+fn test_recurring_invoice_requires_bounded_schedule() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let start = env.ledger().timestamp() + 10;
+    let description = SorobanString::from_str(&env, "Plan");
+
+    let invalid_schedules = [
+        // Unbounded
+        RecurringSchedule { period: 60, start, end_date: None, max_cycles: None },
+        // Zero period
+        RecurringSchedule { period: 0, start, end_date: None, max_cycles: Some(3) },
+        // End not after start
+        RecurringSchedule { period: 60, start, end_date: Some(start), max_cycles: None },
+        // Zero cycles
+        RecurringSchedule { period: 60, start, end_date: None, max_cycles: Some(0) },
+    ];
+
+    for schedule in invalid_schedules.iter() {
+        let result = client.try_create_recurring_invoice(
+            &creator,
+            &client_addr,
+            &100i128,
+            &description,
+            schedule,
+        );
+        assert_eq!(result, Err(Ok(ContractError::InvalidSchedule)));
+    }
+}
+
+/// Late cycles are still due a full period after issue, and pull
+/// authorization does not bypass the client's approval policy
+#[test]
+fn test_late_cycles_and_approval_policy() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let client_addr = Address::generate(&env);
+    let manager = Address::generate(&env);
+    let finance = Address::generate(&env);
+    let start = env.ledger().timestamp() + 100;
+    let period = 3_600;
+
+    let recurring_id = client
+        .create_recurring_invoice(
+            &creator,
+            &client_addr,
+            &2_000,
+            &SorobanString::from_str(&env, "Hosting"),
+            &RecurringSchedule {
+                period,
+                start,
+                end_date: None,
+                max_cycles: Some(3),
+            },
+        )
+        .recurring_id;
+    client.authorize_recurring_payment(&recurring_id, &client_addr);
+    client.set_approval_policy(
+        &client_addr,
+        &ApprovalPolicy {
+            approvers: Vec::from_array(&env, [manager.clone(), finance.clone()]),
+            threshold: 2,
+            ordered: false,
+            min_amount: 0,
+        },
+    );
+
+    // Two cycles late: the missed cycle is not already overdue
+    let now = start + 2 * period + 500;
+    env.ledger().with_mut(|li| {
+        li.timestamp = now;
+    });
+    let issued = client.generate_recurring_invoice(&recurring_id);
+    assert_eq!(issued.status, InvoiceStatus::Sent);
+    let invoice = client.get_invoice(&issued.invoice_id);
+    assert_eq!(invoice.due_date, now + period);
+
+    // The policy's approvers still have to sign off
+    assert_eq!(
+        client.try_execute_invoice(&issued.invoice_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.approve_invoice(&issued.invoice_id, &manager, &1);
+    client.approve_invoice(&issued.invoice_id, &finance, &1);
+    assert_eq!(client.execute_invoice(&issued.invoice_id).status, InvoiceStatus::Executed);
+}
@@ -0,0 +1,120 @@
+//! Property-based tests for P2P refund requests
+//! Feature: stellar-smart-contract-dapp, Property 35: Refunds Capped at Original Amount
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, RefundRequestStatus,
+    TransactionLink,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String as SorobanString};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 35: Refunds Capped at Original Amount
+///
+/// For any confirmed P2P transaction and any sequence of partial refunds,
+/// each approved refund SHALL record a reverse transaction linked to its
+/// request, and the refunded total SHALL equal the sum of approved refunds
+/// and never exceed the original amount.
+///
+/// This test runs 100 iterations with up to five refund attempts of
+/// varying size against each transaction.
+#[test]
+fn property_refunds_capped_at_original_amount() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let amount: i128 = 1_000 + (iteration as i128 * 37);
+        let memo = SorobanString::from_str(&env, "Concert tickets");
+        let original_id = client.execute_p2p_transaction(&sender, &recipient, &amount, &memo).transaction_id;
+        let reason = SorobanString::from_str(&env, "Event cancelled");
+
+        let mut refunded: i128 = 0;
+        for attempt in 0..5u64 {
+            let request_id = match client.try_request_refund(&original_id, &sender, &reason) {
+                Ok(Ok(request_id)) => request_id,
+                result => {
+                    assert_eq!(refunded, amount, "Iteration {}: Only a fully refunded transaction should reject requests", iteration);
+                    assert_eq!(result, Err(Ok(ContractError::RefundExceedsOriginal)));
+                    break;
+                }
+            };
+
+            let refund = amount * (1 + ((iteration + attempt) % 4) as i128) / 5;
+            let result = client.try_approve_refund(&request_id, &recipient, &refund);
+            if refunded + refund > amount {
+                assert_eq!(
+                    result,
+                    Err(Ok(ContractError::RefundExceedsOriginal)),
+                    "Iteration {}: Refunds beyond the original amount should fail",
+                    iteration
+                );
+                assert_eq!(client.get_refund_request(&request_id).status, RefundRequestStatus::Pending);
+                client.decline_refund(&request_id, &recipient);
+                continue;
+            }
+
+            let refund_tx = client.get_transaction(&result.unwrap().unwrap().transaction_id);
+            assert_eq!(refund_tx.sender, recipient);
+            assert_eq!(refund_tx.recipient, sender);
+            assert_eq!(refund_tx.amount, refund);
+            assert_eq!(refund_tx.link, TransactionLink::RefundRequest(request_id));
+            refunded += refund;
+
+            let request = client.get_refund_request(&request_id);
+            assert_eq!(request.status, RefundRequestStatus::Approved);
+            assert_eq!(request.refund_tx_id, Some(refund_tx.id));
+        }
+
+        assert_eq!(client.get_refunded_total(&original_id), refunded);
+        assert!(refunded <= amount);
+    }
+}
+
+/// Only the original parties can request or approve refunds, and refunds
+/// themselves cannot be refunded
+#[test]
+fn test_refund_parties_and_eligibility() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let memo = SorobanString::from_str(&env, "Deposit");
+    let reason = SorobanString::from_str(&env, "Sent twice");
+    let original_id = client.execute_p2p_transaction(&sender, &recipient, &500, &memo).transaction_id;
+
+    assert_eq!(
+        client.try_request_refund(&original_id, &recipient, &reason),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    let request_id = client.request_refund(&original_id, &sender, &reason);
+    assert_eq!(
+        client.try_approve_refund(&request_id, &sender, &500),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    let refund_id = client.approve_refund(&request_id, &recipient, &500).transaction_id;
+    assert_eq!(
+        client.try_approve_refund(&request_id, &recipient, &1),
+        Err(Ok(ContractError::RefundRequestClosed))
+    );
+    assert_eq!(
+        client.try_request_refund(&refund_id, &recipient, &reason),
+        Err(Ok(ContractError::TransactionNotRefundable))
+    );
+    assert_eq!(client.get_refunded_total(&original_id), 500);
+}
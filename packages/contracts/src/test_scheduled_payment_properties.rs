@@ -0,0 +1,122 @@
+//! Property-based tests for future-dated scheduled payments
+//! Feature: stellar-smart-contract-dapp, Property 31: Scheduled Payment Timing
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, ScheduledPaymentStatus,
+    TransactionLink, TransactionType,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String as SorobanString};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 31: Scheduled Payment Timing
+///
+/// For any scheduled payment and any ledger time, execution SHALL fail
+/// before the due time and otherwise record exactly one P2P transaction for
+/// the scheduled amount, after which the payment can neither execute again
+/// nor be cancelled.
+///
+/// This test runs 100 iterations with delays of up to a day and execution
+/// attempts on both sides of the due time.
+#[test]
+fn property_scheduled_payment_timing() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let amount: i128 = 500 + (iteration as i128 * 113);
+        let memo = SorobanString::from_str(&env, "Rent");
+        let now = env.ledger().timestamp();
+        let execute_at = now + 1 + (iteration * 877) % 86_400;
+
+        let payment_id = client.schedule_payment(&sender, &recipient, &amount, &memo, &execute_at);
+
+        // Try just before the due time, then at or after it
+        let early = execute_at - 1 - (iteration % 3).min(execute_at - now - 1);
+        env.ledger().with_mut(|li| {
+            li.timestamp = early;
+        });
+        assert_eq!(
+            client.try_execute_scheduled_payment(&payment_id),
+            Err(Ok(ContractError::PaymentNotDue)),
+            "Iteration {}: Payment should not execute before its due time",
+            iteration
+        );
+        assert_eq!(client.get_scheduled_payment(&payment_id).status, ScheduledPaymentStatus::Pending);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = execute_at + iteration % 4;
+        });
+        let result = client.execute_scheduled_payment(&payment_id);
+        let transaction = client.get_transaction(&result.transaction_id);
+        assert_eq!(transaction.transaction_type, TransactionType::P2P);
+        assert_eq!(transaction.sender, sender);
+        assert_eq!(transaction.recipient, recipient);
+        assert_eq!(transaction.amount, amount);
+        assert_eq!(transaction.link, TransactionLink::ScheduledPayment(payment_id));
+
+        let payment = client.get_scheduled_payment(&payment_id);
+        assert_eq!(payment.status, ScheduledPaymentStatus::Executed);
+        assert_eq!(payment.transaction_id, Some(result.transaction_id));
+        assert_eq!(
+            client.try_execute_scheduled_payment(&payment_id),
+            Err(Ok(ContractError::ScheduledPaymentClosed))
+        );
+        assert_eq!(
+            client.try_cancel_scheduled_payment(&payment_id, &sender),
+            Err(Ok(ContractError::ScheduledPaymentClosed))
+        );
+    }
+}
+
+/// Only the sender can cancel, and a cancelled payment never executes
+#[test]
+fn test_cancelled_payment_never_executes() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let memo = SorobanString::from_str(&env, "Payroll");
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_000;
+    });
+
+    assert_eq!(
+        client.try_schedule_payment(&sender, &recipient, &100, &memo, &1_000),
+        Err(Ok(ContractError::InvalidSchedule))
+    );
+
+    let payment_id = client.schedule_payment(&sender, &recipient, &100, &memo, &2_000);
+    assert_eq!(
+        client.try_cancel_scheduled_payment(&payment_id, &recipient),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    let payment = client.cancel_scheduled_payment(&payment_id, &sender);
+    assert_eq!(payment.status, ScheduledPaymentStatus::Cancelled);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 2_000;
+    });
+    assert_eq!(
+        client.try_execute_scheduled_payment(&payment_id),
+        Err(Ok(ContractError::ScheduledPaymentClosed))
+    );
+    assert_eq!(
+        client.try_execute_scheduled_payment(&99),
+        Err(Ok(ContractError::ScheduledPaymentNotFound))
+    );
+}
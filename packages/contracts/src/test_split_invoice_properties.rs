@@ -0,0 +1,170 @@
+//! Property-based tests for multi-payer invoices
+//! Feature: stellar-smart-contract-dapp, Property 21: Split Invoice Settlement
+//! **Validates: Requirements 4.1, 4.3, 4.5**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, InvoiceStatus,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, Map, String as SorobanString, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 21: Split Invoice Settlement
+///
+/// For any split invoice, the invoice amount SHALL equal the sum of the
+/// shares, each payer SHALL settle only their own share, and the invoice
+/// SHALL be `Executed` exactly when every share is paid.
+///
+/// This test runs 100 iterations with two to six payers.
+#[test]
+fn property_split_invoice_settlement() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let creator = Address::generate(&env);
+        let payer_count = 2 + (iteration % 5) as u32;
+        let mut payers: Vec<Address> = Vec::new(&env);
+        let mut shares: Map<Address, i128> = Map::new(&env);
+        let mut total: i128 = 0;
+        for n in 0..payer_count {
+            let payer = Address::generate(&env);
+            let share = 1_000 + (iteration as i128 * 13) + (n as i128 * 250);
+            shares.set(payer.clone(), share);
+            payers.push_back(payer);
+            total += share;
+        }
+        let due_date = env.ledger().timestamp() + 10_000;
+        let description = SorobanString::from_str(&env, "Team offsite booking");
+
+        let invoice_id = client
+            .create_split_invoice(&creator, &shares, &description, &due_date)
+            .invoice_id;
+        client.mark_invoice_sent(&invoice_id, &creator);
+
+        let invoice = client.get_invoice(&invoice_id);
+        assert_eq!(invoice.amount, total, "Iteration {}: Amount should sum the shares", iteration);
+        assert_eq!(invoice.client, None);
+        assert_eq!(invoice.shares.len(), payer_count);
+
+        // Outsiders have no share, and shares must be approved before payment
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client.try_approve_invoice_share(&invoice_id, &outsider),
+            Err(Ok(ContractError::PayerShareNotFound))
+        );
+        let first = payers.get(0).unwrap();
+        assert_eq!(
+            client.try_pay_invoice_share(&invoice_id, &first),
+            Err(Ok(ContractError::InvalidInvoiceStatus))
+        );
+
+        let mut paid: i128 = 0;
+        for (n, payer) in payers.iter().enumerate() {
+            client.approve_invoice_share(&invoice_id, &payer);
+            let result = client.pay_invoice_share(&invoice_id, &payer);
+            paid += shares.get(payer.clone()).unwrap();
+
+            let invoice = client.get_invoice(&invoice_id);
+            assert_eq!(invoice.paid_amount, paid);
+            let expected = if n as u32 + 1 == payer_count {
+                InvoiceStatus::Executed
+            } else {
+                InvoiceStatus::PartiallyPaid
+            };
+            assert_eq!(
+                result.status, expected,
+                "Iteration {}: Invoice should execute only after the last share",
+                iteration
+            );
+
+            let share = invoice.shares.iter().find(|s| s.payer == payer).unwrap();
+            let settlement = client.get_transaction(&share.settlement_tx_id.unwrap());
+            assert_eq!(settlement.sender, payer);
+            assert_eq!(settlement.recipient, creator);
+            assert_eq!(settlement.amount, share.amount);
+
+            assert_eq!(
+                client.try_pay_invoice_share(&invoice_id, &payer),
+                if expected == InvoiceStatus::Executed {
+                    Err(Ok(ContractError::InvalidInvoiceStatus))
+                } else {
+                    Err(Ok(ContractError::ShareAlreadyPaid))
+                }
+            );
+        }
+    }
+}
+
+/// Unpaid shares can no longer be paid once the due date has passed; the
+/// paid shares stay settled and the invoice is marked partially settled
+#[test]
+fn test_unpaid_shares_expire_at_due_date() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let creator = Address::generate(&env);
+    let payer_a = Address::generate(&env);
+    let payer_b = Address::generate(&env);
+    let mut shares: Map<Address, i128> = Map::new(&env);
+    shares.set(payer_a.clone(), 600);
+    shares.set(payer_b.clone(), 400);
+    let due_date = env.ledger().timestamp() + 1_000;
+    let description = SorobanString::from_str(&env, "Venue hire");
+
+    let invoice_id = client
+        .create_split_invoice(&creator, &shares, &description, &due_date)
+        .invoice_id;
+    client.mark_invoice_sent(&invoice_id, &creator);
+    client.approve_invoice_share(&invoice_id, &payer_a);
+    client.approve_invoice_share(&invoice_id, &payer_b);
+    client.pay_invoice_share(&invoice_id, &payer_a);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = due_date + 1;
+    });
+    assert_eq!(
+        client.try_pay_invoice_share(&invoice_id, &payer_b),
+        Err(Ok(ContractError::InvoiceExpired))
+    );
+
+    let result = client.check_invoice_expiration(&invoice_id);
+    assert_eq!(result.status, InvoiceStatus::PartiallySettled);
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::PartiallySettled);
+    assert_eq!(invoice.paid_amount, 600);
+    let paid = invoice.shares.iter().find(|s| s.payer == payer_a).unwrap();
+    let settlement = client.get_transaction(&paid.settlement_tx_id.unwrap());
+    assert_eq!(settlement.sender, payer_a);
+    assert_eq!(settlement.amount, 600);
+    let unpaid = invoice.shares.iter().find(|s| s.payer == payer_b).unwrap();
+    assert!(!unpaid.paid);
+
+    // A split invoice with no share paid simply expires, also when swept
+    let unpaid_id = client
+        .create_split_invoice(&creator, &shares, &description, &(due_date + 1_000))
+        .invoice_id;
+    client.mark_invoice_sent(&unpaid_id, &creator);
+    let swept_id = client
+        .create_split_invoice(&creator, &shares, &description, &(due_date + 1_000))
+        .invoice_id;
+    client.mark_invoice_sent(&swept_id, &creator);
+    client.approve_invoice_share(&swept_id, &payer_b);
+    client.pay_invoice_share(&swept_id, &payer_b);
+    env.ledger().with_mut(|li| {
+        li.timestamp = due_date + 1_001;
+    });
+    assert_eq!(client.check_invoice_expiration(&unpaid_id).status, InvoiceStatus::Expired);
+    client.sweep_expired_invoices(&swept_id, &1);
+    assert_eq!(client.get_invoice(&swept_id).status, InvoiceStatus::PartiallySettled);
+}
@@ -0,0 +1,138 @@
+//! Property-based tests for linear payment streams
+//! Feature: stellar-smart-contract-dapp, Property 29: Stream Vesting Conservation
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, StreamStatus, TransactionLink,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 29: Stream Vesting Conservation
+///
+/// For any stream and any sequence of withdrawals, the recipient SHALL never
+/// withdraw more than has vested, nothing SHALL be withdrawable before the
+/// cliff, and the withdrawals SHALL sum exactly to the deposit once the
+/// stream has ended.
+///
+/// This test runs 100 iterations with varying deposits, durations, cliffs
+/// and withdrawal times.
+#[test]
+fn property_stream_vesting_conservation() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let deposit: i128 = 1_000 + (iteration as i128 * 7_919);
+        let start = 1_000u64;
+        let duration = 100 + (iteration * 37) % 900;
+        let end = start + duration;
+        let cliff = if iteration % 3 == 0 { None } else { Some(start + duration / (1 + iteration % 4)) };
+
+        let stream_id = client.create_stream(&sender, &recipient, &deposit, &start, &cliff, &end);
+        let cliff_at = cliff.unwrap_or(start);
+
+        let step = 1 + duration / (2 + iteration % 5);
+        let mut total_withdrawn: i128 = 0;
+        let mut now = start;
+        while client.get_stream(&stream_id).status == StreamStatus::Active {
+            now += step;
+            env.ledger().with_mut(|li| {
+                li.timestamp = now;
+            });
+
+            let withdrawable = client.get_stream_withdrawable(&stream_id);
+            if now < cliff_at {
+                assert_eq!(withdrawable, 0, "Iteration {}: Nothing should vest before the cliff", iteration);
+                assert_eq!(
+                    client.try_withdraw_from_stream(&stream_id, &recipient),
+                    Err(Ok(ContractError::NothingToClaim))
+                );
+                continue;
+            }
+
+            let withdrawn = client.withdraw_from_stream(&stream_id, &recipient);
+            assert_eq!(withdrawn, withdrawable);
+            total_withdrawn += withdrawn;
+
+            // Withdrawals track the linear schedule exactly
+            let elapsed = now.min(end) - start;
+            assert_eq!(
+                total_withdrawn,
+                deposit * elapsed as i128 / duration as i128,
+                "Iteration {}: Withdrawn total should match the vested amount",
+                iteration
+            );
+        }
+
+        assert_eq!(total_withdrawn, deposit);
+        let stream = client.get_stream(&stream_id);
+        assert_eq!(stream.status, StreamStatus::Completed);
+        assert_eq!(stream.withdrawn, deposit);
+    }
+}
+
+/// Cancelling pays out the vested portion and refunds the rest
+#[test]
+fn test_cancel_splits_vested_and_unvested() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 500;
+    });
+
+    // Schedules must end in the future with the cliff inside them
+    assert_eq!(
+        client.try_create_stream(&sender, &recipient, &1_000, &1_000, &Some(2_500), &2_000),
+        Err(Ok(ContractError::InvalidSchedule))
+    );
+    assert_eq!(
+        client.try_create_stream(&sender, &recipient, &1_000, &100, &None, &400),
+        Err(Ok(ContractError::InvalidSchedule))
+    );
+
+    let stream_id = client.create_stream(&sender, &recipient, &1_000, &1_000, &None, &2_000);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_250;
+    });
+    assert_eq!(client.withdraw_from_stream(&stream_id, &recipient), 250);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_600;
+    });
+    assert_eq!(
+        client.try_cancel_stream(&stream_id, &recipient),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    let stream = client.cancel_stream(&stream_id, &sender);
+    assert_eq!(stream.status, StreamStatus::Cancelled);
+    assert_eq!(stream.withdrawn, 600);
+    assert_eq!(stream.refunded, 400);
+
+    // The payout on cancellation links back to the stream
+    let payout = client.get_transaction(&2);
+    assert_eq!(payout.amount, 350);
+    assert_eq!(payout.link, TransactionLink::Stream(stream_id));
+
+    assert_eq!(client.get_stream_withdrawable(&stream_id), 0);
+    assert_eq!(
+        client.try_withdraw_from_stream(&stream_id, &recipient),
+        Err(Ok(ContractError::StreamInactive))
+    );
+}
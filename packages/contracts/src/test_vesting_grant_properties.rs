@@ -0,0 +1,152 @@
+//! Property-based tests for tranche-based vesting grants
+//! Feature: stellar-smart-contract-dapp, Property 30: Tranche Vesting Schedule
+//! **Validates: Requirements 3.1, 3.2**
+
+use crate::vesting::TRANCHE_PERIOD;
+use crate::{
+    StellarDAppContract, StellarDAppContractClient, ContractError, TransactionLink,
+};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, Map, Vec};
+
+/// Helper function to create a test environment with initialized contract
+fn setup_test_env() -> (Env, Address, StellarDAppContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(StellarDAppContract, ());
+    let client = StellarDAppContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let _ = client.try_initialize(&admin);
+
+    (env, contract_id, client)
+}
+
+/// Property 30: Tranche Vesting Schedule
+///
+/// For any grant and any point on its schedule, each beneficiary's vested
+/// amount SHALL be zero before the cliff and otherwise cover exactly the
+/// monthly tranches unlocked so far, and claims SHALL sum to each full
+/// allocation once the schedule has ended.
+///
+/// This test runs 100 iterations with one to four beneficiaries, 1 to 24
+/// tranches and cliffs of up to a quarter of the schedule, claiming at
+/// every tranche boundary.
+#[test]
+fn property_tranche_vesting_schedule() {
+    for iteration in 0..100u64 {
+        let (env, _contract_id, client) = setup_test_env();
+
+        let funder = Address::generate(&env);
+        let beneficiary_count = 1 + (iteration % 4) as usize;
+        let mut beneficiaries: Map<Address, i128> = Map::new(&env);
+        let mut addresses: Vec<Address> = Vec::new(&env);
+        for n in 0..beneficiary_count {
+            let beneficiary = Address::generate(&env);
+            beneficiaries.set(beneficiary.clone(), 1_000 + (iteration as i128 * 131) + n as i128 * 17);
+            addresses.push_back(beneficiary);
+        }
+
+        let tranche_count = 1 + (iteration % 24) as u32;
+        let start = 10_000u64;
+        let cliff_tranches = (iteration % 7) as u32 % (tranche_count / 4 + 1);
+        let cliff = start + cliff_tranches as u64 * TRANCHE_PERIOD;
+        let grant_id = client.create_vesting_grant(&funder, &beneficiaries, &start, &cliff, &tranche_count, &None);
+
+        // Visit every tranche boundary, plus a moment just before the cliff
+        for tranche in 0..=tranche_count {
+            let now = start + tranche as u64 * TRANCHE_PERIOD;
+            env.ledger().with_mut(|li| {
+                li.timestamp = if now == cliff && cliff > start { now - 1 } else { now };
+            });
+
+            for beneficiary in addresses.iter() {
+                let allocation = beneficiaries.get(beneficiary.clone()).unwrap();
+                let balance = client.get_vesting_balance(&grant_id, &beneficiary);
+                let unlocked = if env.ledger().timestamp() < cliff { 0 } else { tranche };
+                assert_eq!(
+                    balance.vested,
+                    allocation * unlocked as i128 / tranche_count as i128,
+                    "Iteration {}: Vested amount should cover the unlocked tranches",
+                    iteration
+                );
+
+                if balance.vested > balance.claimed {
+                    let claimed = client.claim_vested_tokens(&grant_id, &beneficiary);
+                    assert_eq!(claimed, balance.vested - balance.claimed);
+                } else {
+                    assert_eq!(
+                        client.try_claim_vested_tokens(&grant_id, &beneficiary),
+                        Err(Ok(ContractError::NothingToClaim))
+                    );
+                }
+            }
+        }
+
+        for beneficiary in addresses.iter() {
+            let balance = client.get_vesting_balance(&grant_id, &beneficiary);
+            assert_eq!(balance.claimed, balance.allocation);
+        }
+    }
+}
+
+/// Revocation freezes vesting and refunds the tranches not yet unlocked
+#[test]
+fn test_revocation_refunds_unvested_tranches() {
+    let (env, _contract_id, client) = setup_test_env();
+
+    let funder = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let mut beneficiaries: Map<Address, i128> = Map::new(&env);
+    beneficiaries.set(alice.clone(), 1_200);
+    beneficiaries.set(bob.clone(), 2_400);
+
+    // The cliff must fall within the schedule
+    assert_eq!(
+        client.try_create_vesting_grant(&funder, &beneficiaries, &0, &(13 * TRANCHE_PERIOD), &12, &None),
+        Err(Ok(ContractError::InvalidSchedule))
+    );
+
+    let grant_id = client.create_vesting_grant(&funder, &beneficiaries, &0, &TRANCHE_PERIOD, &12, &Some(admin.clone()));
+    let fixed_id = client.create_vesting_grant(&funder, &beneficiaries, &0, &0, &12, &None);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 4 * TRANCHE_PERIOD + 10;
+    });
+    assert_eq!(client.claim_vested_tokens(&grant_id, &alice), 400);
+    assert_eq!(
+        client.try_claim_vested_tokens(&grant_id, &outsider),
+        Err(Ok(ContractError::BeneficiaryNotFound))
+    );
+    assert_eq!(
+        client.try_revoke_vesting_grant(&grant_id, &funder),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_revoke_vesting_grant(&fixed_id, &admin),
+        Err(Ok(ContractError::GrantNotRevocable))
+    );
+
+    let grant = client.revoke_vesting_grant(&grant_id, &admin);
+    assert_eq!(grant.refunded, 800 + 1_600);
+    assert_eq!(
+        client.try_revoke_vesting_grant(&grant_id, &admin),
+        Err(Ok(ContractError::GrantNotRevocable))
+    );
+
+    // Tranches unlocked before revocation stay claimable; later ones never unlock
+    env.ledger().with_mut(|li| {
+        li.timestamp = 12 * TRANCHE_PERIOD;
+    });
+    assert_eq!(client.claim_vested_tokens(&grant_id, &bob), 800);
+    let claim = client.get_transaction(&2);
+    assert_eq!(claim.link, TransactionLink::VestingGrant(grant_id));
+    assert_eq!(
+        client.try_claim_vested_tokens(&grant_id, &alice),
+        Err(Ok(ContractError::NothingToClaim))
+    );
+    assert_eq!(client.get_vesting_balance(&fixed_id, &bob).vested, 2_400);
+}
@@ -0,0 +1,41 @@
+//! Test helpers for exercising the contract offline
+
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use crate::{PriceData, PriceOracle};
+
+/// Stellar Mainnet per-transaction resource limits, matching those the SDK
+/// enforces on `Env::default()`
+pub const TX_MAX_INSTRUCTIONS: i64 = 600_000_000;
+pub const TX_MAX_WRITE_ENTRIES: u32 = 50;
+pub const TX_MAX_FOOTPRINT_ENTRIES: u32 = 100;
+
+/// Assert the last contract invocation fits the per-transaction limits
+pub fn assert_within_tx_limits(env: &Env) {
+    let resources = env.cost_estimate().resources();
+    assert!(resources.instructions <= TX_MAX_INSTRUCTIONS);
+    assert!(resources.write_entries <= TX_MAX_WRITE_ENTRIES);
+    assert!(
+        resources.write_entries + resources.memory_read_entries + resources.disk_read_entries
+            <= TX_MAX_FOOTPRINT_ENTRIES
+    );
+}
+
+/// Mock price oracle returning whatever prices were last set on it
+#[contract]
+pub struct MockPriceOracle;
+
+#[contractimpl]
+impl MockPriceOracle {
+    /// Set the price of an asset as reported at `timestamp`
+    pub fn set_price(env: Env, asset: Address, price: i128, timestamp: u64) {
+        env.storage().instance().set(&asset, &PriceData { price, timestamp });
+    }
+}
+
+#[contractimpl]
+impl PriceOracle for MockPriceOracle {
+    /// Most recent price of `asset`, if one was set
+    fn lastprice(env: Env, asset: Address) -> Option<PriceData> {
+        env.storage().instance().get(&asset)
+    }
+}
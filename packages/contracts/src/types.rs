@@ -1,6 +1,6 @@
 //! Type definitions for the Stellar DApp smart contract
 
-use soroban_sdk::{contracttype, contracterror, Address, String, Vec};
+use soroban_sdk::{contracttype, contracterror, Address, BytesN, Map, String, Vec};
 
 /// Contract error types
 #[contracterror]
@@ -33,6 +33,116 @@ pub enum ContractError {
     InvalidSignature = 12,
     /// Reentrancy detected
     ReentrancyDetected = 13,
+    /// Invalid schedule (period, start or end time)
+    InvalidSchedule = 14,
+    /// Recurring invoice not found
+    RecurringInvoiceNotFound = 15,
+    /// Recurring invoice is cancelled or completed
+    RecurringInvoiceInactive = 16,
+    /// Next billing cycle has not started yet
+    CycleNotDue = 17,
+    /// Approval was given for an outdated invoice version
+    InvoiceVersionMismatch = 18,
+    /// Invoice status does not allow the operation
+    InvalidInvoiceStatus = 19,
+    /// Credit note not found
+    CreditNoteNotFound = 20,
+    /// Credits would exceed the amount paid on the invoice
+    CreditExceedsPayment = 21,
+    /// Approval policy is malformed
+    InvalidApprovalPolicy = 22,
+    /// Approver already approved this invoice version
+    DuplicateApproval = 23,
+    /// Approver is not next in an ordered approval policy
+    ApprovalOutOfOrder = 24,
+    /// Secret does not match the stored hash
+    InvalidSecret = 25,
+    /// Invoice already has a client
+    InvoiceAlreadyClaimed = 26,
+    /// Invoice has not been claimed by a client yet
+    InvoiceUnclaimed = 27,
+    /// Invoice has no holdback in the required state
+    HoldbackNotActive = 28,
+    /// Dispute window has already closed
+    DisputeWindowClosed = 29,
+    /// Dispute window is still open
+    DisputeWindowOpen = 30,
+    /// Payer has no share in the invoice
+    PayerShareNotFound = 31,
+    /// Payer share has already been paid
+    ShareAlreadyPaid = 32,
+    /// No price oracle has been registered
+    OracleNotConfigured = 33,
+    /// Oracle price is missing or older than the allowed age
+    PriceUnavailable = 34,
+    /// Conversion rate moved beyond the agreed slippage bound
+    SlippageExceeded = 35,
+    /// External reference is already used by another of the creator's invoices
+    DuplicateReference = 36,
+    /// Payment request not found
+    PaymentRequestNotFound = 37,
+    /// Payment request was already paid or declined
+    PaymentRequestClosed = 38,
+    /// Netting batch not found
+    NettingBatchNotFound = 39,
+    /// Split config not found
+    SplitConfigNotFound = 40,
+    /// Split shares are empty, duplicated or do not sum to 10,000 basis points
+    InvalidSplitConfig = 41,
+    /// Payment stream not found
+    StreamNotFound = 42,
+    /// Payment stream is no longer active
+    StreamInactive = 43,
+    /// Nothing has vested beyond what was already withdrawn
+    NothingToClaim = 44,
+    /// Vesting grant not found
+    GrantNotFound = 45,
+    /// Address is not a beneficiary of the vesting grant
+    BeneficiaryNotFound = 46,
+    /// Vesting grant has no revoker or was already revoked
+    GrantNotRevocable = 47,
+    /// Scheduled payment not found
+    ScheduledPaymentNotFound = 48,
+    /// Scheduled payment was already executed or cancelled
+    ScheduledPaymentClosed = 49,
+    /// Scheduled payment is not due yet
+    PaymentNotDue = 50,
+    /// Transaction is not a pending transfer awaiting a claim
+    TransferNotPending = 51,
+    /// Pending transfer expired before it was claimed
+    TransferExpired = 52,
+    /// Pending transfer has not expired yet
+    TransferNotExpired = 53,
+    /// Payment link not found
+    PaymentLinkNotFound = 54,
+    /// Payment link was already claimed or reclaimed
+    PaymentLinkClosed = 55,
+    /// Payment link expired before it was claimed
+    PaymentLinkExpired = 56,
+    /// Payment link has not expired yet
+    PaymentLinkNotExpired = 57,
+    /// Expense group not found
+    ExpenseGroupNotFound = 58,
+    /// Address is not a member of the expense group
+    NotGroupMember = 59,
+    /// Refund request not found
+    RefundRequestNotFound = 60,
+    /// Refund request was already approved or declined
+    RefundRequestClosed = 61,
+    /// Refund would exceed the amount of the original transaction
+    RefundExceedsOriginal = 62,
+    /// Only confirmed P2P transactions can be refunded
+    TransactionNotRefundable = 63,
+    /// Handle is too short, too long or contains unsupported characters
+    InvalidHandle = 64,
+    /// Handle is already registered
+    HandleTaken = 65,
+    /// Handle is not registered
+    HandleNotFound = 66,
+    /// Arbiter must be independent of the invoice creator and client
+    InvalidArbiter = 67,
+    /// Credit notes cannot be issued against split invoices
+    SplitInvoiceNotCreditable = 68,
 }
 
 /// Transaction status enumeration
@@ -55,6 +165,68 @@ pub enum TransactionType {
     Invoice = 3,
 }
 
+/// Record a transaction was made for
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TransactionLink {
+    None,
+    PaymentRequest(u64),
+    NettingBatch(u64),
+    SplitConfig(u64),
+    Stream(u64),
+    VestingGrant(u64),
+    ScheduledPayment(u64),
+    PaymentLink(u64),
+    ExpenseGroup(u64),
+    RefundRequest(u64),
+    Handle(String),                    // Normalized handle the recipient was resolved from
+}
+
+/// Payment request status enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PaymentRequestStatus {
+    Pending = 0,
+    Paid = 1,
+    Declined = 2,
+}
+
+/// Scheduled payment status enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScheduledPaymentStatus {
+    Pending = 0,
+    Executed = 1,
+    Cancelled = 2,
+}
+
+/// Payment link status enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PaymentLinkStatus {
+    Open = 0,
+    Claimed = 1,
+    Reclaimed = 2,
+}
+
+/// Refund request status enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundRequestStatus {
+    Pending = 0,
+    Approved = 1,
+    Declined = 2,
+}
+
+/// Payment stream status enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamStatus {
+    Active = 0,
+    Completed = 1,
+    Cancelled = 2,
+}
+
 /// Escrow status enumeration
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -76,6 +248,49 @@ pub enum InvoiceStatus {
     Executed = 3,
     Rejected = 4,
     Expired = 5,
+    Cancelled = 6,
+    Escrowed = 7,
+    Refunded = 8,
+    PartiallyPaid = 9,
+    PartiallySettled = 10, // Split invoice past its due date with only some shares paid
+}
+
+/// Recurring invoice status enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecurringInvoiceStatus {
+    Active = 0,
+    Completed = 1,
+    Cancelled = 2,
+}
+
+/// How a credit note is settled with the client
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CreditDisposition {
+    Refund = 0,
+    ApplyToNextInvoice = 1,
+}
+
+/// Credit note status enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CreditNoteStatus {
+    Refunded = 0,
+    Pending = 1,
+    Applied = 2,
+}
+
+/// Holdback (post-settlement dispute window) status enumeration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HoldbackStatus {
+    None = 0,
+    Configured = 1,
+    Holding = 2,
+    Disputed = 3,
+    Released = 4,
+    Refunded = 5,
 }
 
 /// Condition type for escrow
@@ -99,6 +314,81 @@ pub struct Transaction {
     pub status: TransactionStatus,
     pub timestamp: u64,
     pub metadata: String,
+    pub link: TransactionLink,
+}
+
+/// Request from a payee for a payer to send money
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentRequest {
+    pub id: u64,
+    pub payee: Address,
+    pub payer: Address,
+    pub payer_handle: Option<String>,  // Normalized handle the payer was resolved from
+    pub amount: i128,
+    pub memo: String,
+    pub status: PaymentRequestStatus,
+    pub created_at: u64,
+    pub transaction_id: Option<u64>,   // Payment transaction once paid
+}
+
+/// Payment locked now and executed by anyone once due
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledPayment {
+    pub id: u64,
+    pub sender: Address,
+    pub recipient: Address,            // Resolved again from `recipient_handle` at execution
+    pub recipient_handle: Option<String>,
+    pub recipient_handle_owner: Option<Address>, // Handle owner when scheduled; must still own it
+    pub amount: i128,
+    pub memo: String,
+    pub execute_at: u64,
+    pub status: ScheduledPaymentStatus,
+    pub created_at: u64,
+    pub transaction_id: Option<u64>,   // Payment transaction once executed
+}
+
+/// Payment locked under the hash of a claim secret shared as a link
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentLink {
+    pub id: u64,
+    pub sender: Address,
+    pub amount: i128,
+    pub memo: String,
+    pub claim_key: BytesN<32>,         // Ed25519 public key that signs claims
+    pub expires_at: u64,
+    pub status: PaymentLinkStatus,
+    pub created_at: u64,
+    pub claimed_by: Option<Address>,   // Address the payment was claimed to
+    pub transaction_id: Option<u64>,   // Payment transaction once claimed
+}
+
+/// Request from a sender to get money back for a confirmed P2P transaction
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundRequest {
+    pub id: u64,
+    pub transaction_id: u64,           // Original transaction being refunded
+    pub sender: Address,               // Original sender, who receives the refund
+    pub recipient: Address,            // Original recipient, who approves the refund
+    pub reason: String,
+    pub status: RefundRequestStatus,
+    pub refunded_amount: i128,
+    pub refund_tx_id: Option<u64>,     // Reverse transaction once approved
+    pub created_at: u64,
+}
+
+/// Registered handle pointing at the address that receives payments to it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HandleRecord {
+    pub handle: String,                // Normalized (lowercase) handle
+    pub owner: Address,
+    pub target: Address,
+    pub registered_at: u64,
+    pub updated_at: u64,
 }
 
 /// Escrow condition definition
@@ -122,6 +412,7 @@ pub struct EscrowContract {
     pub status: EscrowStatus,
     pub created_at: u64,
     pub expires_at: u64,
+    pub invoice_id: Option<u64>,   // Invoice funded through this escrow
 }
 
 /// Invoice data
@@ -130,13 +421,273 @@ pub struct EscrowContract {
 pub struct Invoice {
     pub id: u64,
     pub creator: Address,
-    pub client: Address,
+    pub client: Option<Address>,   // None until claimed with the approval secret
     pub amount: i128,
     pub description: String,
     pub status: InvoiceStatus,
     pub created_at: u64,
     pub due_date: u64,
     pub approved_at: Option<u64>,
+    pub recurring_id: Option<u64>,
+    pub version: u32,              // Bumped on every amendment
+    pub paid_amount: i128,         // Amount paid by the client at execution
+    pub credited_amount: i128,     // Total of credit notes issued against the invoice
+    pub claim_key: Option<BytesN<32>>, // Ed25519 public key that signs claims
+    pub receivable_holder: Address, // Receives payment; the creator unless the receivable was sold
+    pub settlement_tx_id: Option<u64>,
+    pub escrow_id: Option<u64>,    // Escrow funding the invoice for pay-on-delivery
+    pub holdback_status: HoldbackStatus,
+    pub holdback_window: u64,              // Seconds after execution during which disputes can be raised
+    pub holdback_arbiter: Option<Address>,
+    pub holdback_release_at: Option<u64>,  // Set when the invoice is executed
+    pub dispute_reason: Option<String>,
+    pub shares: Vec<PayerShare>,           // Per-payer shares; empty for single-client invoices
+    pub denomination_asset: Option<Address>, // Asset the amount is priced in; None when not oracle-priced
+    pub settlement_asset: Option<Address>,   // Asset the client pays in
+    pub max_price_age: u64,                // Maximum oracle price age in seconds
+    pub max_slippage_bps: u32,             // Adverse rate move the client accepted at approval
+    pub quoted_rate: i128,                 // Rate quoted at approval (settlement units per denomination unit, 7 decimals)
+    pub settled_amount: i128,              // Amount paid in the settlement asset
+    pub creator_number: u64,               // Sequential number among the creator's invoices, from 1
+    pub number_prefix: Option<String>,     // Creator's numbering prefix when the invoice was created
+    pub external_ref: Option<String>,      // Off-chain reference (e.g. backend record ID), unique per creator
+    pub netting_batch: Option<u64>,        // Netting batch the invoice was settled in
+}
+
+/// One net payment between two parties
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NetTransfer {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// Set of mutual invoices settled together by net transfers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NettingBatch {
+    pub id: u64,
+    pub invoice_ids: Vec<u64>,
+    pub gross_amount: i128,           // Sum of the invoice amounts settled
+    pub transfers: Vec<NetTransfer>,
+    pub transaction_ids: Vec<u64>,    // One transaction per net transfer, in the same order
+    pub created_at: u64,
+}
+
+/// One payer's fixed share of a multi-payer invoice
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayerShare {
+    pub payer: Address,
+    pub amount: i128,
+    pub approved: bool,
+    pub paid: bool,
+    pub settlement_tx_id: Option<u64>,
+}
+
+/// Price reported by an oracle, in the oracle's base asset and decimals
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Deposit vesting linearly per second from sender to recipient
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentStream {
+    pub id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub deposit: i128,
+    pub withdrawn: i128,             // Total withdrawn by the recipient
+    pub refunded: i128,              // Unvested remainder returned to the sender on cancellation
+    pub start: u64,
+    pub cliff: u64,                  // Nothing is withdrawable before this time; equals start without a cliff
+    pub end: u64,
+    pub status: StreamStatus,
+    pub created_at: u64,
+}
+
+/// One beneficiary's allocation within a vesting grant
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrantBeneficiary {
+    pub beneficiary: Address,
+    pub allocation: i128,
+    pub claimed: i128,
+}
+
+/// Grant vesting in monthly tranches to several beneficiaries from one deposit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingGrant {
+    pub id: u64,
+    pub funder: Address,
+    pub beneficiaries: Vec<GrantBeneficiary>,
+    pub total: i128,
+    pub start: u64,
+    pub cliff: u64,                  // No tranche is claimable before this time
+    pub tranche_count: u32,          // Number of monthly tranches from start
+    pub revoker: Option<Address>,    // Admin allowed to revoke unvested tranches, if any
+    pub revoked_at: Option<u64>,
+    pub refunded: i128,              // Unvested allocation returned to the funder on revocation
+    pub created_at: u64,
+}
+
+/// A beneficiary's vested and claimed amounts under a vesting grant
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingBalance {
+    pub allocation: i128,
+    pub vested: i128,
+    pub claimed: i128,
+}
+
+/// Shared-expense group tracking what each member is owed or owes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpenseGroup {
+    pub id: u64,
+    pub creator: Address,
+    pub members: Vec<Address>,
+    pub balances: Map<Address, i128>,  // Positive when owed to the member, negative when owed by it
+    pub expense_count: u32,
+    pub settlement_count: u32,
+    pub created_at: u64,
+}
+
+/// One recipient's share of a payment split, in basis points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitShare {
+    pub recipient: Address,            // Filled in from `handle` when one is given
+    pub bps: u32,
+    pub handle: Option<String>,        // Handle resolved again at every payment
+    pub handle_owner: Option<Address>, // Filled in with the handle owner; must still own it
+}
+
+/// Reusable percentage split of P2P payments across several recipients
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitConfig {
+    pub id: u64,
+    pub owner: Address,
+    pub shares: Vec<SplitShare>,      // Sum to exactly 10,000 basis points
+    pub created_at: u64,
+}
+
+/// One leg of a batch payout
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutEntry {
+    pub recipient: Address,
+    pub amount: i128,
+    pub memo: String,
+}
+
+/// One leg of a batch payout addressed to a handle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HandlePayoutEntry {
+    pub handle: String,
+    pub amount: i128,
+    pub memo: String,
+}
+
+/// Terms of one invoice in a batch creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceSpec {
+    pub client: Address,
+    pub amount: i128,
+    pub description: String,
+    pub due_date: u64,
+    pub external_ref: Option<String>,
+}
+
+/// Transfer of the right to receive payment on an invoice
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceivableTransfer {
+    pub from: Address,
+    pub to: Address,
+    pub timestamp: u64,
+}
+
+/// Billing schedule of a recurring invoice
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurringSchedule {
+    pub period: u64,               // Billing period in seconds
+    pub start: u64,                // Start of the first billing period
+    pub end_date: Option<u64>,     // No cycle may start at or after this time
+    pub max_cycles: Option<u32>,   // Maximum number of invoices to issue
+}
+
+/// Invoice status history entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceStatusEntry {
+    pub status: InvoiceStatus,
+    pub actor: Option<Address>,    // None for transitions triggered by anyone (e.g. expiry)
+    pub timestamp: u64,
+    pub note: Option<String>,      // Rejection reason or other remark
+}
+
+/// Credit note issued against an executed invoice
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditNote {
+    pub id: u64,
+    pub invoice_id: u64,
+    pub creator: Address,
+    pub client: Address,
+    pub asset: Option<Address>,    // Denomination asset of the credited invoice; None when not oracle-priced
+    pub amount: i128,
+    pub remaining: i128,           // Credit not yet applied to a later invoice
+    pub reason: String,
+    pub disposition: CreditDisposition,
+    pub status: CreditNoteStatus,
+    pub created_at: u64,
+    pub refund_tx_id: Option<u64>,
+}
+
+/// Client-side approval policy for invoices
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalPolicy {
+    pub approvers: Vec<Address>,
+    pub threshold: u32,            // Approvals required (k of n)
+    pub ordered: bool,             // Approvals must follow the order of `approvers`
+    pub min_amount: i128,          // Below this amount a single approval suffices
+}
+
+/// Individual approval of an invoice version
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceApproval {
+    pub approver: Address,
+    pub version: u32,
+    pub timestamp: u64,
+}
+
+/// Recurring invoice template for subscription billing
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurringInvoice {
+    pub id: u64,
+    pub creator: Address,
+    pub client: Address,
+    pub amount: i128,
+    pub description: String,
+    pub schedule: RecurringSchedule,
+    pub cycles_issued: u32,
+    pub pull_authorized: bool,     // Client pre-authorized payment of each cycle
+    pub status: RecurringInvoiceStatus,
+    pub created_at: u64,
 }
 
 /// Transaction result
@@ -164,4 +715,28 @@ pub struct InvoiceResult {
     pub invoice_id: u64,
     pub status: InvoiceStatus,
     pub tx_hash: Option<String>,
-}
\ No newline at end of file
+}
+
+/// Recurring invoice result
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurringInvoiceResult {
+    pub recurring_id: u64,
+    pub status: RecurringInvoiceStatus,
+}
+
+/// Credit note result
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditNoteResult {
+    pub credit_note_id: u64,
+    pub status: CreditNoteStatus,
+}
+
+/// Expiration sweep result
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepResult {
+    pub expired: Vec<u64>,        // IDs of invoices expired by this sweep
+    pub next_id: Option<u64>,     // Where to resume, or None once every invoice was scanned
+}
@@ -1,6 +1,6 @@
 //! Validation utilities for smart contract operations
 
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Bytes, BytesN, Env};
 use crate::ContractError;
 
 /// Validate that an address is properly formatted
@@ -53,6 +53,21 @@ pub fn validate_signature(_env: &Env, signer: &Address, _data: &[u8], _signature
     Ok(())
 }
 
+/// Validate that `signature` is an ed25519 signature of `message` by
+/// `claim_key`.
+///
+/// The host traps on an invalid signature, so the invocation fails with a
+/// host crypto error rather than a `ContractError`, and any storage changes
+/// are rolled back.
+pub fn validate_claim_signature(
+    env: &Env,
+    claim_key: &BytesN<32>,
+    message: &Bytes,
+    signature: &BytesN<64>,
+) {
+    env.crypto().ed25519_verify(claim_key, message, signature);
+}
+
 /// Check for reentrancy attacks
 pub fn check_reentrancy(env: &Env) -> Result<(), ContractError> {
     // Simple reentrancy guard using contract storage
@@ -0,0 +1,215 @@
+//! Grant-style vesting in monthly tranches for several beneficiaries
+
+use soroban_sdk::{contractimpl, Address, Env, Map, String, Vec};
+use crate::{
+    StellarDAppContract, StellarDAppContractArgs, StellarDAppContractClient, ContractError,
+    GrantBeneficiary, TransactionLink, TransactionType, VestingBalance, VestingGrant,
+};
+use crate::validation;
+use crate::storage;
+
+/// Length of one vesting tranche (30 days)
+pub const TRANCHE_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+/// Maximum number of beneficiaries on a single vesting grant
+const MAX_GRANT_BENEFICIARIES: u32 = 20;
+
+#[contractimpl]
+impl StellarDAppContract {
+    /// Fund a vesting grant for several beneficiaries from one deposit
+    /// (called by funder)
+    ///
+    /// `beneficiaries` maps each beneficiary to their allocation. Each
+    /// allocation unlocks in `tranche_count` equal monthly tranches counted
+    /// from `start`; tranches that unlock before `cliff` become claimable at
+    /// the cliff. If `revoker` is set, that address may revoke the grant,
+    /// returning every tranche not yet unlocked to the funder.
+    pub fn create_vesting_grant(
+        env: Env,
+        funder: Address,
+        beneficiaries: Map<Address, i128>,
+        start: u64,
+        cliff: u64,
+        tranche_count: u32,
+        revoker: Option<Address>,
+    ) -> Result<u64, ContractError> {
+        // Validate inputs
+        validation::validate_address(&env, &funder)?;
+        if beneficiaries.is_empty() || beneficiaries.len() > MAX_GRANT_BENEFICIARIES {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut allocations = Vec::new(&env);
+        let mut total: i128 = 0;
+        for (beneficiary, allocation) in beneficiaries.iter() {
+            validation::validate_amount(allocation)?;
+            total = total.checked_add(allocation).ok_or(ContractError::InvalidAmount)?;
+            allocations.push_back(GrantBeneficiary {
+                beneficiary,
+                allocation,
+                claimed: 0,
+            });
+        }
+
+        // Validate the cliff falls within a non-empty schedule
+        let end = (tranche_count as u64)
+            .checked_mul(TRANCHE_PERIOD)
+            .and_then(|duration| start.checked_add(duration))
+            .ok_or(ContractError::InvalidSchedule)?;
+        if tranche_count == 0 || cliff < start || cliff > end {
+            return Err(ContractError::InvalidSchedule);
+        }
+
+        // Lock the deposit (in real implementation, transfer funds from funder to contract)
+        validation::validate_balance(&env, &funder, total)?;
+
+        let grant = VestingGrant {
+            id: storage::get_next_vesting_grant_id(&env),
+            funder,
+            beneficiaries: allocations,
+            total,
+            start,
+            cliff,
+            tranche_count,
+            revoker,
+            revoked_at: None,
+            refunded: 0,
+            created_at: env.ledger().timestamp(),
+        };
+        storage::set_vesting_grant(&env, grant.id, &grant);
+
+        Ok(grant.id)
+    }
+
+    /// Claim every unlocked tranche not yet claimed (called by beneficiary)
+    ///
+    /// Tranches unlocked before a revocation stay claimable afterwards.
+    /// Returns the amount claimed.
+    pub fn claim_vested_tokens(env: Env, grant_id: u64, beneficiary: Address) -> Result<i128, ContractError> {
+        // Check for reentrancy
+        validation::check_reentrancy(&env)?;
+
+        let result = Self::claim_vested(&env, grant_id, beneficiary);
+
+        // Clear reentrancy guard
+        validation::clear_reentrancy(&env);
+
+        result
+    }
+
+    /// Revoke a grant, returning tranches not yet unlocked to the funder
+    /// (called by the grant's revoker)
+    pub fn revoke_vesting_grant(env: Env, grant_id: u64, revoker: Address) -> Result<VestingGrant, ContractError> {
+        let mut grant = storage::get_vesting_grant(&env, grant_id)
+            .ok_or(ContractError::GrantNotFound)?;
+
+        if grant.revoked_at.is_some() {
+            return Err(ContractError::GrantNotRevocable);
+        }
+        match &grant.revoker {
+            Some(grant_revoker) if *grant_revoker == revoker => {}
+            Some(_) => return Err(ContractError::Unauthorized),
+            None => return Err(ContractError::GrantNotRevocable),
+        }
+
+        // Verify revoker authorization
+        validation::validate_signature(&env, &revoker, &[], &[])?;
+
+        let now = env.ledger().timestamp();
+        grant.revoked_at = Some(now);
+        let tranches = Self::unlocked_tranches(&grant, now);
+        for entry in grant.beneficiaries.iter() {
+            grant.refunded += entry.allocation - Self::tranche_amount(&grant, &entry, tranches);
+        }
+
+        // Refund the unvested remainder (in real implementation, transfer back to funder)
+        storage::set_vesting_grant(&env, grant_id, &grant);
+
+        Ok(grant)
+    }
+
+    /// Get vesting grant details
+    pub fn get_vesting_grant(env: Env, grant_id: u64) -> Result<VestingGrant, ContractError> {
+        storage::get_vesting_grant(&env, grant_id)
+            .ok_or(ContractError::GrantNotFound)
+    }
+
+    /// Get a beneficiary's allocation, vested and claimed amounts
+    pub fn get_vesting_balance(
+        env: Env,
+        grant_id: u64,
+        beneficiary: Address,
+    ) -> Result<VestingBalance, ContractError> {
+        let grant = storage::get_vesting_grant(&env, grant_id)
+            .ok_or(ContractError::GrantNotFound)?;
+        let index = Self::beneficiary_index(&grant, &beneficiary)?;
+        let entry = grant.beneficiaries.get(index).unwrap();
+
+        let tranches = Self::unlocked_tranches(&grant, env.ledger().timestamp());
+        Ok(VestingBalance {
+            allocation: entry.allocation,
+            vested: Self::tranche_amount(&grant, &entry, tranches),
+            claimed: entry.claimed,
+        })
+    }
+
+    /// Number of tranches unlocked at `now`, frozen at revocation
+    fn unlocked_tranches(grant: &VestingGrant, now: u64) -> u32 {
+        let at = grant.revoked_at.map_or(now, |revoked_at| revoked_at.min(now));
+        if at < grant.cliff {
+            return 0;
+        }
+        ((at - grant.start) / TRANCHE_PERIOD).min(grant.tranche_count as u64) as u32
+    }
+
+    /// Portion of a beneficiary's allocation covered by `tranches`
+    fn tranche_amount(grant: &VestingGrant, entry: &GrantBeneficiary, tranches: u32) -> i128 {
+        // Split the multiplication so `allocation * tranches` cannot overflow
+        let count = grant.tranche_count as i128;
+        let tranches = tranches as i128;
+        (entry.allocation / count) * tranches + (entry.allocation % count) * tranches / count
+    }
+
+    /// Find a beneficiary's position within a grant
+    fn beneficiary_index(grant: &VestingGrant, beneficiary: &Address) -> Result<u32, ContractError> {
+        grant.beneficiaries
+            .iter()
+            .position(|entry| entry.beneficiary == *beneficiary)
+            .map(|index| index as u32)
+            .ok_or(ContractError::BeneficiaryNotFound)
+    }
+
+    /// Pay a beneficiary their unlocked, unclaimed tranches
+    fn claim_vested(env: &Env, grant_id: u64, beneficiary: Address) -> Result<i128, ContractError> {
+        let mut grant = storage::get_vesting_grant(env, grant_id)
+            .ok_or(ContractError::GrantNotFound)?;
+        let index = Self::beneficiary_index(&grant, &beneficiary)?;
+        let mut entry = grant.beneficiaries.get(index).unwrap();
+
+        // Verify beneficiary authorization
+        validation::validate_signature(env, &beneficiary, &[], &[])?;
+
+        let tranches = Self::unlocked_tranches(&grant, env.ledger().timestamp());
+        let amount = Self::tranche_amount(&grant, &entry, tranches) - entry.claimed;
+        if amount <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+
+        // Record the claim (in real implementation, transfer funds from contract to beneficiary)
+        Self::record_transaction(
+            env,
+            TransactionType::P2P,
+            grant.funder.clone(),
+            beneficiary,
+            amount,
+            String::from_str(env, "vesting"),
+            TransactionLink::VestingGrant(grant_id),
+        );
+
+        entry.claimed += amount;
+        grant.beneficiaries.set(index, entry);
+        storage::set_vesting_grant(env, grant_id, &grant);
+
+        Ok(amount)
+    }
+}